@@ -0,0 +1,59 @@
+use charflow::backend::WriterBackend;
+use charflow::colors::{GREEN, GREY, LIGHT_GREY_50};
+use charflow::elements::simple::Text;
+use charflow::input::Input;
+use charflow::style::Attributes;
+use charflow::terminal::{SessionOptions, Viewport};
+use charflow::tui::{StaticTUI, TUI};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+fn main() {
+    let viewport = Viewport::Inline(3);
+    let tui = StaticTUI::with_backend(Box::new(WriterBackend::stdout_with_viewport(viewport)));
+
+    let title = Text {
+        x: 1,
+        y: 1,
+        z: 0,
+        text_color: Some(LIGHT_GREY_50),
+        bg_color: None,
+        attributes: Attributes {
+            italic: true,
+            ..Default::default()
+        },
+        visible: true,
+        text: "Downloading [bold]packages[/]".to_string(),
+        markup: true,
+    };
+    let progress = Arc::new(RwLock::new(Text {
+        x: 1,
+        y: 2,
+        z: 0,
+        text_color: Some(GREEN),
+        bg_color: Some(GREY),
+        attributes: Attributes::default(),
+        visible: true,
+        text: String::new(),
+        markup: false,
+    }));
+    tui.write()
+        .unwrap()
+        .elements
+        .push(Arc::new(RwLock::new(title)));
+    tui.write().unwrap().elements.push(progress.clone());
+
+    let mut input = Input::with_options(
+        false,
+        SessionOptions {
+            viewport,
+            ..Default::default()
+        },
+    );
+
+    for step in 0..=40 {
+        progress.write().unwrap().text = "#".repeat(step) + &" ".repeat(40 - step);
+        tui.read().unwrap().update();
+        input.update(Duration::from_millis(50));
+    }
+}
@@ -1,4 +1,3 @@
-use charflow;
 use charflow::colors::{GREY, ORANGE, ORANGE_50, YELLOW};
 use charflow::elements::complex::element_tree::{
     Element, Folder, Icon, Part, CLOSED_FOLDER, OPEN_FOLDER, SIMPLE_SET,
@@ -7,7 +6,10 @@ use charflow::elements::complex::ElementTree;
 use charflow::elements::simple::line_box::LINES_HEAVY;
 use charflow::elements::simple::{Box, Button, Text, TextBox};
 use charflow::input::callbacks::{CHAR_BACKSPACE, MOUSE_LEFT_PRESS, UPPERCASE_J, UPPERCASE_K};
+use charflow::input::observers::TuiResizeObserver;
 use charflow::input::{Action, Event, Input};
+use charflow::style::{Attributes, Underline};
+use charflow::terminal::SessionOptions;
 use charflow::tui::{ReactiveTUI, TUI};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
@@ -19,11 +21,7 @@ fn folder(name: String, is_open: bool, parts: Option<Vec<Part>>) -> Folder {
         icon_closed_id: "closed".to_string(),
         icon_open_id: "open".to_string(),
         is_open,
-        children: if let Some(children) = parts {
-            children
-        } else {
-            Vec::new()
-        },
+        children: parts.unwrap_or_default(),
     }
 }
 
@@ -87,6 +85,7 @@ fn main() {
         element_color: Some(ORANGE),
         line_color: None,
         bg_color: Some(GREY),
+        attributes: Attributes::default(),
         visible: true,
     };
 
@@ -105,8 +104,10 @@ fn main() {
         height: 15,
         text_color: Some(ORANGE),
         bg_color: Some(GREY),
+        attributes: Attributes::default(),
         visible: true,
-        text: "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Praesent sed lorem sit amet elit ullamcorper gravida ut vitae dolor. Cras.".to_string()
+        text: "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Praesent sed lorem sit amet elit ullamcorper gravida ut vitae dolor. Cras.".to_string(),
+        markup: false,
     };
 
     tui.write()
@@ -124,6 +125,7 @@ fn main() {
         height: 3,
         line_color: Some(YELLOW),
         bg_color: Some(GREY),
+        attributes: Attributes::default(),
         visible: true,
         line_set: LINES_HEAVY,
     };
@@ -139,39 +141,34 @@ fn main() {
         width: 13,
         height: 2,
         selected: false,
-        action: Action {
-            0: Arc::new(move |data: Event| match data {
-                Event::KeyEvent(key_event) => match key_event.code {
-                    CHAR_BACKSPACE => {
-                        let mut line_box_write = line_box_rw_lock.write().unwrap();
-                        if line_box_write.bg_color == Some(GREY) {
-                            line_box_write.bg_color = Some(ORANGE_50)
-                        } else {
-                            line_box_write.bg_color = Some(GREY)
-                        }
-                    }
-                    _ => {}
-                },
-                Event::MouseEvent(mouse_event) => match mouse_event.code {
-                    MOUSE_LEFT_PRESS => {
-                        let mut line_box_write = line_box_rw_lock.write().unwrap();
-                        if line_box_write.bg_color == Some(GREY) {
-                            line_box_write.bg_color = Some(ORANGE_50)
-                        } else {
-                            line_box_write.bg_color = Some(GREY)
-                        }
-                    }
-                    _ => {}
-                },
-            }),
-        },
+        action: Action(Arc::new(move |data: Event| {
+            let pressed = match data {
+                Event::KeyEvent(key_event) => key_event.code == CHAR_BACKSPACE,
+                Event::MouseEvent(mouse_event) => mouse_event.code == MOUSE_LEFT_PRESS,
+            };
+            if pressed {
+                let mut line_box_write = line_box_rw_lock.write().unwrap();
+                if line_box_write.bg_color == Some(GREY) {
+                    line_box_write.bg_color = Some(ORANGE_50)
+                } else {
+                    line_box_write.bg_color = Some(GREY)
+                }
+            }
+        })),
         text_color: None,
         bg_color: Some(GREY),
         selected_text_color: Some(YELLOW),
         selected_bg_color: Some(ORANGE_50),
+        attributes: Attributes::default(),
+        selected_attributes: Attributes {
+            bold: true,
+            underline: Underline::Single,
+            ..Default::default()
+        },
         enabled: true,
         visible: true,
         text: "This is a button.".to_string(),
+        markup: false,
     };
 
     let button_rw_lock = Arc::new(RwLock::new(button));
@@ -194,10 +191,17 @@ fn main() {
         bg_color: Some(GREY),
         selected_text_color: Some(YELLOW),
         selected_bg_color: Some(ORANGE_50),
+        attributes: Attributes::default(),
+        selected_attributes: Attributes {
+            bold: true,
+            underline: Underline::Single,
+            ..Default::default()
+        },
         enabled: true,
         visible: true,
         action: Action::default(),
         text: "This is also a button!".to_string(),
+        markup: false,
     };
 
     let second_button_rw_lock = Arc::new(RwLock::new(second_button));
@@ -218,8 +222,10 @@ fn main() {
         z: 0,
         text_color: Some(ORANGE),
         bg_color: Some(GREY),
+        attributes: Attributes::default(),
         visible: true,
         text: "-0\n-1\n-2\n-3\n-4\n-5\n-6\n-7\n-8\n-9\n10\n11\n12\n13\n14".to_string(),
+        markup: false,
     };
 
     tui.write()
@@ -228,7 +234,13 @@ fn main() {
         .push(Arc::new(RwLock::new(text)));
     // End of text example.
 
-    let mut input = Input::new(true, false);
+    let mut input = Input::with_options(
+        true,
+        SessionOptions {
+            alternate_screen: true,
+            ..Default::default()
+        },
+    );
 
     input.key_observers.write().unwrap().push(tui_key_observer);
     input
@@ -236,6 +248,11 @@ fn main() {
         .write()
         .unwrap()
         .push(tui_mouse_observer);
+    input
+        .resize_observers
+        .write()
+        .unwrap()
+        .push(Arc::new(TuiResizeObserver { tui: tui.clone() }));
 
     loop {
         tui.read().unwrap().update();
@@ -0,0 +1,360 @@
+//! Output targets rendered frames are written to.
+
+use std::io::{self, Stdout, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::{env, fs};
+
+use crate::buffer::{to_escapes, Buffer, Cell};
+use crate::cursor::{Cursor, HIDE_CURSOR};
+use crate::input::get_size;
+use crate::recorder::Recorder;
+use crate::terminal::Viewport;
+
+/// Structs that implement this trait can be used as render output.
+pub trait Backend: Send {
+    /// Returns the output size as width and height.
+    fn get_size(&self) -> (u16, u16);
+    /// Draws the given cells.
+    fn draw(&mut self, cells: &[(u16, u16, &Cell)]) -> io::Result<()>;
+    /// Flushes everything drawn so far.
+    fn flush(&mut self) -> io::Result<()>;
+    /// Places the cursor after the cells of a frame were drawn, hides it if none.
+    ///
+    /// Backends without a cursor ignore it.
+    fn set_cursor(&mut self, _cursor: Option<Cursor>) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Returns whether the next frame has to be drawn completely and resets the request.
+    fn take_full_frame(&mut self) -> bool {
+        false
+    }
+}
+
+/// Backend that writes ansi escape sequences to any writer.
+///
+/// Everything drawn is collected and written in one go on flush.
+pub struct WriterBackend<W: Write + Send> {
+    /// Output the escape sequences are written to.
+    writer: W,
+    /// Fixed output size, the terminal size is used if none.
+    size: Option<(u16, u16)>,
+    /// Part of the terminal drawn on.
+    viewport: Viewport,
+    /// Cursor currently shown, none if hidden.
+    cursor: Option<Cursor>,
+    /// Output of the current frame, written at once on flush.
+    frame: Vec<u8>,
+    /// Wrap frames in synchronized update mode.
+    synchronized_output: bool,
+    /// Records each written frame.
+    recorder: Option<Recorder>,
+    /// The next frame has to be drawn completely.
+    full_frame: bool,
+}
+
+/// Backend writing to the terminal.
+pub type StdoutBackend = WriterBackend<Stdout>;
+
+impl WriterBackend<Stdout> {
+    /// Creates a backend writing to stdout, sized like the terminal.
+    pub fn stdout() -> StdoutBackend {
+        WriterBackend::stdout_with_viewport(Viewport::Fullscreen)
+    }
+
+    /// Creates a backend writing to stdout, drawing on the viewport.
+    ///
+    /// The viewport has to match the one of the active [Session](crate::terminal::Session).
+    pub fn stdout_with_viewport(viewport: Viewport) -> StdoutBackend {
+        WriterBackend {
+            writer: io::stdout(),
+            size: None,
+            viewport,
+            cursor: None,
+            frame: Vec::new(),
+            synchronized_output: detect_synchronized_output(),
+            recorder: None,
+            full_frame: false,
+        }
+    }
+}
+
+impl<W: Write + Send> WriterBackend<W> {
+    /// Creates a backend writing to the writer with a fixed size.
+    pub fn new(writer: W, width: u16, height: u16) -> WriterBackend<W> {
+        WriterBackend {
+            writer,
+            size: Some((width, height)),
+            viewport: Viewport::Fullscreen,
+            cursor: None,
+            frame: Vec::new(),
+            synchronized_output: false,
+            recorder: None,
+            full_frame: false,
+        }
+    }
+
+    /// Sets the part of the terminal drawn on.
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        self.viewport = viewport;
+    }
+
+    /// Sets a fixed output size, if none the terminal size is used.
+    pub fn set_size(&mut self, size: Option<(u16, u16)>) {
+        self.size = size;
+    }
+
+    /// Sets whether frames are wrapped in synchronized update mode, so the terminal shows them
+    /// at once instead of while they are written.
+    ///
+    /// Enabled for stdout when the terminal is known to support it, see
+    /// [Capabilities::synchronized_output](crate::input::capabilities::Capabilities::synchronized_output)
+    /// to find out for others.
+    pub fn set_synchronized_output(&mut self, synchronized_output: bool) {
+        self.synchronized_output = synchronized_output;
+    }
+
+    /// Sets the recorder frames are recorded with, stops recording if none.
+    ///
+    /// The next frame is drawn completely so the recording starts with the whole screen.
+    pub fn set_recorder(&mut self, recorder: Option<Recorder>) {
+        self.full_frame |= recorder.is_some();
+        self.recorder = recorder;
+    }
+
+    /// Returns a reference to the writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write + Send> Backend for WriterBackend<W> {
+    fn get_size(&self) -> (u16, u16) {
+        let (width, height) = self.size.unwrap_or_else(|| {
+            let (height, width) = get_size();
+            (width, height)
+        });
+        match self.viewport {
+            Viewport::Fullscreen => (width, height),
+            Viewport::Inline(lines) => (width, lines),
+        }
+    }
+
+    fn draw(&mut self, cells: &[(u16, u16, &Cell)]) -> io::Result<()> {
+        let mut output = to_escapes(cells, self.viewport);
+        if output.is_empty() {
+            return Ok(());
+        }
+        // drawing moves the cursor, keep it hidden until it is placed again
+        if self.cursor.take().is_some() {
+            output.insert_str(0, HIDE_CURSOR);
+        }
+        self.frame.extend_from_slice(output.as_bytes());
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.frame.is_empty() {
+            let mut frame = std::mem::take(&mut self.frame);
+            if self.synchronized_output {
+                frame.splice(0..0, BEGIN_SYNCHRONIZED_UPDATE.bytes());
+                frame.extend_from_slice(END_SYNCHRONIZED_UPDATE.as_bytes());
+            }
+            self.writer.write_all(&frame)?;
+            if let Some(recorder) = &self.recorder {
+                recorder.record_output(&frame)?;
+            }
+        }
+        self.writer.flush()
+    }
+
+    fn set_cursor(&mut self, cursor: Option<Cursor>) -> io::Result<()> {
+        if cursor == self.cursor {
+            return Ok(());
+        }
+        let output = match cursor {
+            Some(cursor) => cursor.to_escape(self.viewport),
+            None => HIDE_CURSOR.to_string(),
+        };
+        self.cursor = cursor;
+        self.frame.extend_from_slice(output.as_bytes());
+        Ok(())
+    }
+
+    fn take_full_frame(&mut self) -> bool {
+        std::mem::take(&mut self.full_frame)
+    }
+}
+
+/// Escape sequence making the terminal hold back output until the update ends.
+pub const BEGIN_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026h";
+
+/// Escape sequence showing everything written since the update began.
+pub const END_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026l";
+
+/// Returns whether the terminal is known to support synchronized output from the environment.
+fn detect_synchronized_output() -> bool {
+    let term = env::var("TERM").unwrap_or_default();
+    let program = env::var("TERM_PROGRAM").unwrap_or_default();
+    ["kitty", "foot", "wezterm", "ghostty", "contour"]
+        .iter()
+        .any(|name| term.contains(name) || program.to_lowercase().contains(name))
+}
+
+/// In memory writer that can be cloned to read back what was written.
+///
+/// All clones share the same content.
+#[derive(Clone, Default, Debug)]
+pub struct MemoryOutput(Arc<Mutex<Vec<u8>>>);
+
+impl MemoryOutput {
+    #[allow(missing_docs)]
+    pub fn new() -> MemoryOutput {
+        MemoryOutput::default()
+    }
+
+    /// Returns a copy of everything written so far.
+    pub fn get_content(&self) -> Vec<u8> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Returns everything written so far as string, invalid utf-8 is replaced.
+    pub fn get_string(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+
+    /// Removes all written content.
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+impl Write for MemoryOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Headless backend with a fixed size that records the drawn cells and the cursor.
+///
+/// All clones share the same screen, keep one to inspect what was drawn.
+#[derive(Clone, Debug)]
+pub struct TestBackend {
+    /// Cells currently on the screen.
+    screen: Arc<Mutex<Buffer>>,
+    /// Cursor currently shown.
+    cursor: Arc<Mutex<Option<Cursor>>>,
+}
+
+impl TestBackend {
+    #[allow(missing_docs)]
+    pub fn new(width: u16, height: u16) -> TestBackend {
+        TestBackend {
+            screen: Arc::new(Mutex::new(Buffer::new(width, height))),
+            cursor: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the cursor currently shown, none if hidden.
+    pub fn get_cursor(&self) -> Option<Cursor> {
+        *self.cursor.lock().unwrap()
+    }
+
+    /// Changes the screen size, clearing it like a terminal does.
+    pub fn resize(&self, width: u16, height: u16) {
+        *self.screen.lock().unwrap() = Buffer::new(width, height);
+    }
+
+    /// Returns a copy of the cells currently on the screen.
+    pub fn get_buffer(&self) -> Buffer {
+        self.screen.lock().unwrap().clone()
+    }
+
+    /// Returns the symbols of each screen row.
+    pub fn get_lines(&self) -> Vec<String> {
+        self.screen.lock().unwrap().get_lines()
+    }
+
+    /// Panics if the screen rows don't match the expected lines.
+    pub fn assert_lines(&self, expected: &[&str]) {
+        let lines = self.get_lines();
+        assert_eq!(
+            lines,
+            expected,
+            "screen doesn't match:\n{}\nexpected:\n{}",
+            lines.join("\n"),
+            expected.join("\n")
+        );
+    }
+
+    /// Panics if the screen doesn't match the text snapshot stored at the path.
+    ///
+    /// Set `CHARFLOW_UPDATE_SNAPSHOTS` to create missing snapshots and overwrite existing ones.
+    pub fn assert_snapshot(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let lines = self.get_lines().join("\n") + "\n";
+        if env::var_os("CHARFLOW_UPDATE_SNAPSHOTS").is_some() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).unwrap();
+            }
+            fs::write(path, &lines).unwrap();
+            return;
+        }
+        let snapshot = fs::read_to_string(path).unwrap_or_else(|_| {
+            panic!(
+                "missing snapshot {}, set CHARFLOW_UPDATE_SNAPSHOTS to create it:\n{}",
+                path.display(),
+                lines
+            )
+        });
+        assert_eq!(
+            lines,
+            snapshot,
+            "screen doesn't match snapshot {}:\n{}",
+            path.display(),
+            lines
+        );
+    }
+}
+
+impl Backend for TestBackend {
+    fn get_size(&self) -> (u16, u16) {
+        let screen = self.screen.lock().unwrap();
+        (screen.width, screen.height)
+    }
+
+    fn draw(&mut self, cells: &[(u16, u16, &Cell)]) -> io::Result<()> {
+        let mut screen = self.screen.lock().unwrap();
+        for (x, y, cell) in cells {
+            if let Some(screen_cell) = screen.get_mut(*x, *y) {
+                *screen_cell = (*cell).clone();
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn set_cursor(&mut self, cursor: Option<Cursor>) -> io::Result<()> {
+        *self.cursor.lock().unwrap() = cursor;
+        Ok(())
+    }
+}
@@ -0,0 +1,311 @@
+//! Frame buffer that elements render into.
+
+use crate::colors::Color;
+use crate::layout::Rect;
+use crate::style::{blend, Style};
+use crate::terminal::Viewport;
+use crate::unicode;
+
+/// Describes one character cell of the terminal.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Cell {
+    /// Content of the cell, a single grapheme cluster.
+    ///
+    /// Empty if the cell is covered by the wide grapheme in the cell to its left.
+    pub symbol: String,
+    /// Colors and attributes.
+    pub style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            symbol: " ".to_string(),
+            style: Style::default(),
+        }
+    }
+}
+
+/// Grid of cells representing one frame.
+///
+/// Coordinates are terminal coordinates, starting at 1 in the top left corner.
+/// Writes outside of the buffer are ignored.
+///
+/// While printing inside a view, see [Buffer::with_view], coordinates are relative to the view
+/// and writes outside of it are ignored.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Buffer {
+    /// Buffer width.
+    pub width: u16,
+    /// Buffer height.
+    pub height: u16,
+    /// All cells row by row.
+    cells: Vec<Cell>,
+    /// Added to coordinates, the origin of the current view less one.
+    ///
+    /// Negative if the view starts before the buffer, for example while scrolled.
+    offset: (i32, i32),
+    /// Cells that can be written, in terminal coordinates.
+    clip: Rect,
+}
+
+impl Buffer {
+    /// Creates an empty buffer of the given size.
+    pub fn new(width: u16, height: u16) -> Buffer {
+        Buffer {
+            width,
+            height,
+            cells: vec![Cell::default(); width as usize * height as usize],
+            offset: (0, 0),
+            clip: Rect::new(1, 1, width, height),
+        }
+    }
+
+    /// Returns the index of the cell at the coordinates.
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        if x == 0 || y == 0 {
+            return None;
+        }
+        let x = u16::try_from(x as i32 + self.offset.0).ok()?;
+        let y = u16::try_from(y as i32 + self.offset.1).ok()?;
+        if !self.clip.contains(x, y) {
+            return None;
+        }
+        Some((y - 1) as usize * self.width as usize + (x - 1) as usize)
+    }
+
+    /// Returns the writable part of the current view, in its coordinates.
+    ///
+    /// Without a view this is the whole buffer.
+    pub fn get_area(&self) -> Rect {
+        Rect::new(
+            (self.clip.x as i32 - self.offset.0).max(0) as u16,
+            (self.clip.y as i32 - self.offset.1).max(0) as u16,
+            self.clip.width,
+            self.clip.height,
+        )
+    }
+
+    /// Returns the area in terminal coordinates, without the part before the buffer.
+    fn to_terminal(&self, area: Rect) -> Rect {
+        let x = area.x as i32 + self.offset.0;
+        let y = area.y as i32 + self.offset.1;
+        let cut = |start: i32, size: u16| {
+            let end = (start + size as i32).clamp(1, u16::MAX as i32);
+            let start = start.clamp(1, u16::MAX as i32);
+            (start as u16, (end - start) as u16)
+        };
+        let (x, width) = cut(x, area.width);
+        let (y, height) = cut(y, area.height);
+        Rect::new(x, y, width, height)
+    }
+
+    /// Runs the print function with writes outside of the area ignored.
+    ///
+    /// The area is in coordinates of the current view, which stay the same.
+    pub fn with_clip<R>(&mut self, area: Rect, print: impl FnOnce(&mut Buffer) -> R) -> R {
+        let previous = self.clip;
+        self.clip = self.clip.intersection(self.to_terminal(area));
+        let result = print(self);
+        self.clip = previous;
+        result
+    }
+
+    /// Runs the print function with coordinates relative to the area, 1, 1 being its top left
+    /// corner. Writes outside of the area are ignored.
+    ///
+    /// The area is in coordinates of the current view, views can be nested.
+    pub fn with_view<R>(&mut self, area: Rect, print: impl FnOnce(&mut Buffer) -> R) -> R {
+        self.with_scrolled_view(area, (0, 0), print)
+    }
+
+    /// Runs the print function with coordinates relative to the area moved by the scroll
+    /// offset, the top left corner of the area shows 1 + scroll x, 1 + scroll y. Writes outside
+    /// of the area are ignored.
+    ///
+    /// The area is in coordinates of the current view, views can be nested.
+    pub fn with_scrolled_view<R>(
+        &mut self,
+        area: Rect,
+        scroll: (u16, u16),
+        print: impl FnOnce(&mut Buffer) -> R,
+    ) -> R {
+        let previous = (self.offset, self.clip);
+        let (offset_x, offset_y) = self.offset;
+        self.clip = self.clip.intersection(self.to_terminal(area));
+        self.offset = (
+            offset_x + area.x as i32 - 1 - scroll.0 as i32,
+            offset_y + area.y as i32 - 1 - scroll.1 as i32,
+        );
+        let result = print(self);
+        (self.offset, self.clip) = previous;
+        result
+    }
+
+    /// Returns the cell at the coordinates.
+    pub fn get(&self, x: u16, y: u16) -> Option<&Cell> {
+        self.index(x, y).map(|i| &self.cells[i])
+    }
+
+    /// Returns the mutable cell at the coordinates.
+    pub fn get_mut(&mut self, x: u16, y: u16) -> Option<&mut Cell> {
+        self.index(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// Sets the content and style of the cell at the coordinates.
+    ///
+    /// Translucent colors are blended with the style of the cell, see [Style::over].
+    ///
+    /// Wide characters also cover the cell to the right.
+    pub fn set(&mut self, x: u16, y: u16, symbol: char, style: Style) {
+        self.set_string(x, y, &symbol.to_string(), style);
+    }
+
+    /// Writes the text from the coordinates to the right, one grapheme cluster at a time.
+    ///
+    /// Returns the number of cells the text took up.
+    pub fn set_string(&mut self, x: u16, y: u16, text: &str, style: Style) -> u16 {
+        let area = self.get_area();
+        let right = area.right().saturating_sub(1);
+        let mut cell_x = x;
+        for grapheme in unicode::graphemes(text) {
+            let width = unicode::grapheme_width(grapheme) as u16;
+            if width == 0 {
+                continue;
+            }
+            if cell_x > right {
+                break;
+            }
+            if cell_x.saturating_add(width - 1) > right {
+                // wide grapheme cut off at the edge
+                self.set_symbol(cell_x, y, " ", style);
+            } else if cell_x < area.x {
+                // wide grapheme cut off at the left edge of a view or clip
+                for i in 0..width {
+                    self.set_symbol(cell_x + i, y, " ", style);
+                }
+            } else {
+                self.set_symbol(cell_x, y, grapheme, style);
+                for i in 1..width {
+                    self.set_symbol(cell_x + i, y, "", style);
+                }
+            }
+            cell_x = cell_x.saturating_add(width);
+        }
+        cell_x.saturating_sub(x)
+    }
+
+    /// Sets a single cell, replacing wide graphemes that would only be partly visible.
+    fn set_symbol(&mut self, x: u16, y: u16, symbol: &str, style: Style) {
+        let Some(cell) = self.get(x, y) else {
+            return;
+        };
+        if cell.symbol.is_empty() && !symbol.is_empty() {
+            // covering the right half of a wide grapheme
+            if let Some(left) = self.get_mut(x.saturating_sub(1), y) {
+                left.symbol = " ".to_string();
+            }
+        }
+        if unicode::width(&self.get(x, y).unwrap().symbol) > 1 {
+            // covering the left half of a wide grapheme
+            if let Some(right) = self.get_mut(x.saturating_add(1), y) {
+                if right.symbol.is_empty() {
+                    right.symbol = " ".to_string();
+                }
+            }
+        }
+        let cell = self.get_mut(x, y).unwrap();
+        cell.symbol = symbol.to_string();
+        cell.style = style.over(cell.style);
+    }
+
+    /// Blends the color over the foreground and background of all cells in the area, keeping
+    /// their content.
+    ///
+    /// Cells without a background take the color as is, ones without a foreground keep the
+    /// terminal default.
+    pub fn tint(&mut self, x: u16, y: u16, width: u16, height: u16, color: Color) {
+        for cell_y in y..y.saturating_add(height) {
+            for cell_x in x..x.saturating_add(width) {
+                let Some(cell) = self.get_mut(cell_x, cell_y) else {
+                    continue;
+                };
+                cell.style.fg_color = cell.style.fg_color.map(|fg| color.over(fg));
+                cell.style.bg_color = blend(color, cell.style.bg_color);
+            }
+        }
+    }
+
+    /// Returns all cells together with their coordinates.
+    pub fn content(&self) -> Vec<(u16, u16, &Cell)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                (
+                    (i % self.width as usize) as u16 + 1,
+                    (i / self.width as usize) as u16 + 1,
+                    cell,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the symbols of each row.
+    pub fn get_lines(&self) -> Vec<String> {
+        if self.width == 0 {
+            return Vec::new();
+        }
+        self.cells
+            .chunks(self.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol.as_str()).collect())
+            .collect()
+    }
+
+    /// Returns the cells of the next buffer that differ from this one.
+    ///
+    /// If the sizes don't match all cells of the next buffer are returned.
+    pub fn diff<'a>(&self, next: &'a Buffer) -> Vec<(u16, u16, &'a Cell)> {
+        if self.width != next.width || self.height != next.height {
+            return next.content();
+        }
+        next.content()
+            .into_iter()
+            .zip(self.cells.iter())
+            .filter(|((_, _, next_cell), cell)| next_cell != cell)
+            .map(|(next_cell, _)| next_cell)
+            .collect()
+    }
+}
+
+/// Returns the escape sequence that draws the given cells in the viewport.
+///
+/// Cursor moves and style changes are only emitted where needed.
+pub fn to_escapes(cells: &[(u16, u16, &Cell)], viewport: Viewport) -> String {
+    let mut output = String::new();
+    let mut position: Option<(u16, u16)> = None;
+    let mut style: Option<Style> = None;
+    for (x, y, cell) in cells {
+        if cell.symbol.is_empty() {
+            // drawn together with the wide grapheme to the left
+            continue;
+        }
+        if position != Some((*x, *y)) {
+            output += &viewport.move_cursor(*x, *y);
+            if let Viewport::Inline(_) = viewport {
+                // restoring the cursor also restores its style
+                style = None;
+            }
+        }
+        if style != Some(cell.style) {
+            output += &cell.style.to_escape();
+            style = Some(cell.style);
+        }
+        output += &cell.symbol;
+        position = Some((x + unicode::width(&cell.symbol).max(1) as u16, *y));
+    }
+    if !output.is_empty() {
+        output += "\x1b[0m";
+    }
+    output
+}
@@ -1,12 +1,28 @@
 //! Color constants and functions.
 #![allow(missing_docs)]
 
+use crate::errors::ParseColorError;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-/// Defines an rgb color.
+/// Defines an rgb color with an alpha channel.
+///
+/// Translucent colors are blended with the cells underneath when drawn, see [Color::over].
+/// Terminals only display opaque colors, the alpha channel is ignored if there is nothing to blend
+/// with.
+///
+/// Serialized as a `#rrggbb` or `#rrggbbaa` hex code, see [FromStr](Color::from_str) for accepted
+/// formats.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct Color {
     /// Red value.
     pub r: u8,
@@ -14,16 +30,459 @@ pub struct Color {
     pub g: u8,
     /// Blue value.
     pub b: u8,
+    /// Opacity, 0 is fully transparent and 255 opaque.
+    pub a: u8,
+}
+
+impl Color {
+    /// Creates an opaque color.
+    pub const fn new(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b, a: 0xFF }
+    }
+
+    #[allow(missing_docs)]
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color { r, g, b, a }
+    }
+
+    /// Returns the color with the opacity replaced.
+    pub const fn with_alpha(self, a: u8) -> Color {
+        Color { a, ..self }
+    }
+
+    /// Returns the color with the opacity scaled by the factor, from 0 to 1.
+    pub fn fade(self, factor: f32) -> Color {
+        self.with_alpha((self.a as f32 * factor.clamp(0.0, 1.0)).round() as u8)
+    }
+
+    /// Returns whether the color is fully opaque.
+    pub const fn is_opaque(&self) -> bool {
+        self.a == 0xFF
+    }
+
+    /// Returns the color drawn over the other one.
+    pub fn over(self, below: Color) -> Color {
+        if self.is_opaque() || below.a == 0 {
+            return self;
+        }
+        let top = self.a as f32 / 255.0;
+        let bottom = below.a as f32 / 255.0 * (1.0 - top);
+        let alpha = top + bottom;
+        if alpha == 0.0 {
+            return Color::rgba(0, 0, 0, 0);
+        }
+        let channel = |a: u8, b: u8| ((a as f32 * top + b as f32 * bottom) / alpha).round() as u8;
+        Color {
+            r: channel(self.r, below.r),
+            g: channel(self.g, below.g),
+            b: channel(self.b, below.b),
+            a: (alpha * 255.0).round() as u8,
+        }
+    }
+
+    /// Creates a color from a `0xrrggbb` value.
+    pub const fn from_u32(rgb: u32) -> Color {
+        Color {
+            r: (rgb >> 16) as u8,
+            g: (rgb >> 8) as u8,
+            b: rgb as u8,
+            a: 0xFF,
+        }
+    }
+
+    /// Creates a color from hue in degrees, saturation and lightness from 0 to 1.
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        Color::from_chroma(hue, chroma, lightness - chroma / 2.0)
+    }
+
+    /// Returns hue in degrees, saturation and lightness from 0 to 1.
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (hue, max, min) = self.hue_max_min();
+        let lightness = (max + min) / 2.0;
+        let saturation = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        (hue, saturation, lightness)
+    }
+
+    /// Creates a color from hue in degrees, saturation and value from 0 to 1.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Color {
+        let chroma = value.clamp(0.0, 1.0) * saturation.clamp(0.0, 1.0);
+        Color::from_chroma(hue, chroma, value.clamp(0.0, 1.0) - chroma)
+    }
+
+    /// Returns hue in degrees, saturation and value from 0 to 1.
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let (hue, max, min) = self.hue_max_min();
+        let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+        (hue, saturation, max)
+    }
+
+    /// Returns the color with the lightness raised by the amount, from 0 to 1.
+    pub fn lighten(&self, amount: f32) -> Color {
+        let (hue, saturation, lightness) = self.to_hsl();
+        Color::from_hsl(hue, saturation, lightness + amount).with_alpha(self.a)
+    }
+
+    /// Returns the color with the lightness lowered by the amount, from 0 to 1.
+    pub fn darken(&self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+
+    /// Returns the mix of both colors, a ratio of 0 is this color and 1 the other.
+    pub fn mix(&self, other: Color, ratio: f32) -> Color {
+        let ratio = ratio.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * ratio).round() as u8;
+        Color {
+            r: channel(self.r, other.r),
+            g: channel(self.g, other.g),
+            b: channel(self.b, other.b),
+            a: channel(self.a, other.a),
+        }
+    }
+
+    /// Returns the relative luminance as defined by WCAG, from 0 to 1.
+    pub fn luminance(&self) -> f32 {
+        let linear = |channel: u8| {
+            let channel = channel as f32 / 255.0;
+            if channel <= 0.03928 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /// Returns the WCAG contrast ratio between the colors, from 1 to 21.
+    pub fn contrast_ratio(&self, other: Color) -> f32 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Returns the candidate with the highest contrast to this color.
+    pub fn best_contrast(&self, candidates: &[Color]) -> Option<Color> {
+        candidates
+            .iter()
+            .copied()
+            .max_by(|a, b| self.contrast_ratio(*a).total_cmp(&self.contrast_ratio(*b)))
+    }
+
+    /// Returns [BLACK] or [WHITE], whichever is more readable on this background color.
+    pub fn readable_foreground(&self) -> Color {
+        if self.contrast_ratio(BLACK) >= self.contrast_ratio(WHITE) {
+            BLACK
+        } else {
+            WHITE
+        }
+    }
+
+    /// Returns hue in degrees and the largest and smallest channel from 0 to 1.
+    fn hue_max_min(&self) -> (f32, f32, f32) {
+        let (r, g, b) = (
+            self.r as f32 / 255.0,
+            self.g as f32 / 255.0,
+            self.b as f32 / 255.0,
+        );
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (hue, max, min)
+    }
+
+    /// Creates a color from hue, chroma and the amount added to every channel.
+    fn from_chroma(hue: f32, chroma: f32, offset: f32) -> Color {
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let channel = |value: f32| ((value + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+        Color {
+            r: channel(r),
+            g: channel(g),
+            b: channel(b),
+            a: 0xFF,
+        }
+    }
+}
+
+/// Number of colors a terminal can display.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ColorDepth {
+    /// No colors, only attributes are used.
+    Monochrome,
+    /// The 16 standard and bright colors, their look depends on the terminal theme.
+    Ansi16,
+    /// The xterm 256 color palette.
+    Ansi256,
+    /// 24-bit rgb colors.
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Returns the ansi escape sequence setting the foreground to the nearest available color.
+    pub fn fg_escape(&self, color: Color) -> String {
+        match self {
+            ColorDepth::Monochrome => String::new(),
+            ColorDepth::Ansi16 => match color.to_ansi16() {
+                index @ 0..=7 => format!("\x1b[{}m", 30 + index),
+                index => format!("\x1b[{}m", 82 + index),
+            },
+            ColorDepth::Ansi256 => format!("\x1b[38;5;{}m", color.to_ansi256()),
+            ColorDepth::TrueColor => format!("\x1b[38;2;{};{};{}m", color.r, color.g, color.b),
+        }
+    }
+
+    /// Returns the ansi escape sequence setting the background to the nearest available color.
+    pub fn bg_escape(&self, color: Color) -> String {
+        match self {
+            ColorDepth::Monochrome => String::new(),
+            ColorDepth::Ansi16 => match color.to_ansi16() {
+                index @ 0..=7 => format!("\x1b[{}m", 40 + index),
+                index => format!("\x1b[{}m", 92 + index),
+            },
+            ColorDepth::Ansi256 => format!("\x1b[48;5;{}m", color.to_ansi256()),
+            ColorDepth::TrueColor => format!("\x1b[48;2;{};{};{}m", color.r, color.g, color.b),
+        }
+    }
+
+    /// Returns the ansi escape sequence setting the underline color to the nearest available
+    /// color.
+    pub fn underline_escape(&self, color: Color) -> String {
+        match self {
+            ColorDepth::Monochrome => String::new(),
+            ColorDepth::Ansi16 => format!("\x1b[58;5;{}m", color.to_ansi16()),
+            ColorDepth::Ansi256 => format!("\x1b[58;5;{}m", color.to_ansi256()),
+            ColorDepth::TrueColor => format!("\x1b[58;2;{};{};{}m", color.r, color.g, color.b),
+        }
+    }
 }
 
+/// Forced color depth, detected on first use if none.
+static COLOR_DEPTH: Mutex<Option<ColorDepth>> = Mutex::new(None);
+
+/// Returns the color depth used for escape sequences.
+///
+/// Detected on first use, see [detect_color_depth] and [set_color_depth].
+pub fn get_color_depth() -> ColorDepth {
+    *COLOR_DEPTH
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(detect_color_depth)
+}
+
+/// Forces the color depth used for escape sequences, none detects it again.
+pub fn set_color_depth(depth: Option<ColorDepth>) {
+    *COLOR_DEPTH.lock().unwrap_or_else(|e| e.into_inner()) = depth;
+}
+
+/// Detects the color depth of the terminal from the environment.
+///
+/// `NO_COLOR` disables colors, `COLORTERM` can announce true color support, otherwise `TERM` and
+/// its terminfo entry decide.
+pub fn detect_color_depth() -> ColorDepth {
+    detect_color_depth_with(|name| env::var(name).ok())
+}
+
+/// Detects the color depth like [detect_color_depth], reading variables through the lookup
+/// instead of the environment.
+pub fn detect_color_depth_with(var: impl Fn(&str) -> Option<String>) -> ColorDepth {
+    if var("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        return ColorDepth::Monochrome;
+    }
+    if let Some(colorterm) = var("COLORTERM") {
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorDepth::TrueColor;
+        }
+    }
+    let Some(term) = var("TERM") else {
+        return ColorDepth::Monochrome;
+    };
+    if term.is_empty() || term == "dumb" {
+        return ColorDepth::Monochrome;
+    }
+    if term.ends_with("-direct") || term.contains("truecolor") || term.contains("24bit") {
+        return ColorDepth::TrueColor;
+    }
+    match terminfo_colors(&term, &var) {
+        Some(colors) if colors >= 1 << 24 => ColorDepth::TrueColor,
+        Some(colors) if colors >= 256 => ColorDepth::Ansi256,
+        Some(colors) if colors >= 8 => ColorDepth::Ansi16,
+        Some(_) => ColorDepth::Monochrome,
+        None if term.contains("256color") => ColorDepth::Ansi256,
+        None => ColorDepth::Ansi16,
+    }
+}
+
+/// Reads the number of colors from the compiled terminfo entry of the terminal, none if there
+/// is no readable entry.
+fn terminfo_colors(term: &str, var: impl Fn(&str) -> Option<String>) -> Option<u32> {
+    let first = term.chars().next()?;
+    let mut directories = Vec::new();
+    if let Some(directory) = var("TERMINFO") {
+        directories.push(PathBuf::from(directory));
+    }
+    if let Some(home) = var("HOME") {
+        directories.push(Path::new(&home).join(".terminfo"));
+    }
+    if let Some(dirs) = var("TERMINFO_DIRS") {
+        directories.extend(dirs.split(':').filter(|d| !d.is_empty()).map(PathBuf::from));
+    }
+    directories.extend(
+        ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo"]
+            .iter()
+            .map(PathBuf::from),
+    );
+    let data = directories.iter().find_map(|directory| {
+        // entries are grouped by first letter, or its hex code on some systems
+        fs::read(directory.join(first.to_string()).join(term))
+            .or_else(|_| fs::read(directory.join(format!("{:x}", first as u32)).join(term)))
+            .ok()
+    })?;
+
+    let short = |i: usize| Some(u16::from_le_bytes([*data.get(i)?, *data.get(i + 1)?]));
+    // the extended format stores numbers as 32-bit
+    let number_size = match short(0)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
+    };
+    let names_size = short(2)? as usize;
+    let bools_count = short(4)? as usize;
+    let numbers_count = short(6)? as usize;
+    // colors is the 14th number, terminals without colors may leave it out
+    if numbers_count <= 13 {
+        return Some(0);
+    }
+    let mut offset = 12 + names_size + bools_count;
+    // numbers start at an even offset
+    offset += offset % 2;
+    offset += 13 * number_size;
+    let colors = if number_size == 2 {
+        match short(offset)? {
+            0xFFFF | 0xFFFE => 0,
+            colors => colors as u32,
+        }
+    } else {
+        let colors = i32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?);
+        u32::try_from(colors).unwrap_or_default()
+    };
+    Some(colors)
+}
+
+impl Color {
+    /// Returns the index of the nearest color in the xterm 256 color palette.
+    ///
+    /// Only the color cube and grayscale ramp are used, their look does not depend on the
+    /// terminal theme.
+    pub fn to_ansi256(&self) -> u8 {
+        // cube levels are 0, 95, 135, 175, 215, 255
+        let level = |value: u8| -> u8 {
+            if value < 48 {
+                0
+            } else if value < 115 {
+                1
+            } else {
+                (value - 35) / 40
+            }
+        };
+        let (r, g, b) = (level(self.r), level(self.g), level(self.b));
+        let cube_value = |level: u8| if level == 0 { 0 } else { 55 + level * 40 };
+        let cube = Color {
+            r: cube_value(r),
+            g: cube_value(g),
+            b: cube_value(b),
+            a: 0xFF,
+        };
+
+        // gray ramp goes from 8 to 238 in steps of 10
+        let average = (self.r as u16 + self.g as u16 + self.b as u16) / 3;
+        let gray_index = (average.saturating_sub(3) / 10).min(23) as u8;
+        let gray_value = 8 + gray_index * 10;
+        let gray = Color {
+            r: gray_value,
+            g: gray_value,
+            b: gray_value,
+            a: 0xFF,
+        };
+
+        if self.distance(gray) < self.distance(cube) {
+            232 + gray_index
+        } else {
+            16 + 36 * r + 6 * g + b
+        }
+    }
+
+    /// Returns the index of the nearest of the 16 standard colors, using the xterm defaults.
+    pub fn to_ansi16(&self) -> u8 {
+        ANSI16
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, color)| self.distance(**color))
+            .map(|(index, _)| index as u8)
+            .unwrap_or_default()
+    }
+
+    /// Returns the squared euclidean distance between the colors.
+    fn distance(&self, other: Color) -> u32 {
+        let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+        channel(self.r, other.r) + channel(self.g, other.g) + channel(self.b, other.b)
+    }
+}
+
+/// The 16 standard colors as xterm displays them by default.
+const ANSI16: [Color; 16] = [
+    Color::new(0, 0, 0),
+    Color::new(205, 0, 0),
+    Color::new(0, 205, 0),
+    Color::new(205, 205, 0),
+    Color::new(0, 0, 238),
+    Color::new(205, 0, 205),
+    Color::new(0, 205, 205),
+    Color::new(229, 229, 229),
+    Color::new(127, 127, 127),
+    Color::new(255, 0, 0),
+    Color::new(0, 255, 0),
+    Color::new(255, 255, 0),
+    Color::new(92, 92, 255),
+    Color::new(255, 0, 255),
+    Color::new(0, 255, 255),
+    Color::new(255, 255, 255),
+];
+
 /// Returns ansi escape sequence to set color as foreground.
+///
+/// The color is mapped to the nearest one available, see [get_color_depth].
 pub fn fg_color_to_string(color: Color) -> String {
-    format!("\x1b[38;2;{};{};{}m", color.r, color.g, color.b)
+    get_color_depth().fg_escape(color)
 }
 
 /// Returns ansi escape sequence to set color as background.
+///
+/// The color is mapped to the nearest one available, see [get_color_depth].
 pub fn bg_color_to_string(color: Color) -> String {
-    format!("\x1b[48;2;{};{};{}m", color.r, color.g, color.b)
+    get_color_depth().bg_escape(color)
 }
 
 /// Forces the use of given fg/bg colors if not given uses terminal default
@@ -43,86 +502,398 @@ pub fn force_colors(fg_color: Option<Color>, bg_color: Option<Color>) -> String
     )
 }
 
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parses `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa` hex codes, `rgb(r, g, b)`,
+    /// `rgba(r, g, b, a)` with alpha from 0 to 1, `transparent`, css color names and palette
+    /// constant names, names are case insensitive and css names take precedence.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = || ParseColorError {
+            text: text.to_string(),
+        };
+        let trimmed = text.trim();
+        if let Some(hex) = trimmed.strip_prefix('#') {
+            // from_str_radix would also accept signs
+            if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(error());
+            }
+            let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| error());
+            // short forms repeat each digit, #fc0 is #ffcc00
+            let (size, factor) = match hex.len() {
+                3 | 4 => (1, 17),
+                6 | 8 => (2, 1),
+                _ => return Err(error()),
+            };
+            let channels = (0..hex.len() / size)
+                .map(|i| Ok(channel(&hex[i * size..(i + 1) * size])? * factor))
+                .collect::<Result<Vec<u8>, _>>()?;
+            return Ok(Color {
+                r: channels[0],
+                g: channels[1],
+                b: channels[2],
+                a: channels.get(3).copied().unwrap_or(0xFF),
+            });
+        }
+        let function = |name: &str| {
+            trimmed
+                .strip_prefix(name)
+                .and_then(|rest| rest.strip_prefix('('))
+                .and_then(|rest| rest.strip_suffix(')'))
+                .map(|arguments| arguments.split(',').map(str::trim).collect::<Vec<_>>())
+        };
+        if let Some(arguments) = function("rgb") {
+            let [r, g, b] = arguments[..] else {
+                return Err(error());
+            };
+            let channel = |value: &str| value.parse::<u8>().map_err(|_| error());
+            return Ok(Color::new(channel(r)?, channel(g)?, channel(b)?));
+        }
+        if let Some(arguments) = function("rgba") {
+            let [r, g, b, a] = arguments[..] else {
+                return Err(error());
+            };
+            let channel = |value: &str| value.parse::<u8>().map_err(|_| error());
+            let alpha = a.parse::<f32>().map_err(|_| error())?;
+            if !(0.0..=1.0).contains(&alpha) {
+                return Err(error());
+            }
+            return Ok(Color::rgba(
+                channel(r)?,
+                channel(g)?,
+                channel(b)?,
+                (alpha * 255.0).round() as u8,
+            ));
+        }
+        if trimmed.eq_ignore_ascii_case("transparent") {
+            return Ok(Color::rgba(0, 0, 0, 0));
+        }
+        let name = trimmed.to_ascii_uppercase();
+        CSS_COLORS
+            .iter()
+            .find(|(css_name, _)| css_name.eq_ignore_ascii_case(&name))
+            .map(|(_, rgb)| Color::from_u32(*rgb))
+            .or_else(|| palette_color(&name))
+            .ok_or_else(error)
+    }
+}
+
+impl fmt::Display for Color {
+    /// Formats the color as a `#rrggbb` hex code, `#rrggbbaa` if translucent.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)?;
+        if !self.is_opaque() {
+            write!(f, "{:02x}", self.a)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        color.to_string()
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = ParseColorError;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+/// Returns the palette constant with the upper case name.
+fn palette_color(name: &str) -> Option<Color> {
+    Some(match name {
+        "YELLOW" => YELLOW,
+        "ORANGE" => ORANGE,
+        "ORANGE_75" => ORANGE_75,
+        "ORANGE_50" => ORANGE_50,
+        "RED" => RED,
+        "RED_75" => RED_75,
+        "RED_50" => RED_50,
+        "LIME" => LIME,
+        "GREEN" => GREEN,
+        "GREEN_75" => GREEN_75,
+        "GREEN_50" => GREEN_50,
+        "WHITE" => WHITE,
+        "LIGHT_GREY" => LIGHT_GREY,
+        "LIGHT_GREY_75" => LIGHT_GREY_75,
+        "LIGHT_GREY_50" => LIGHT_GREY_50,
+        "BLACK" => BLACK,
+        "GREY" => GREY,
+        _ => return None,
+    })
+}
+
 pub const YELLOW: Color = Color {
     r: 0xFF,
     g: 0xCC,
     b: 0x00,
+    a: 0xFF,
 };
 
 pub const ORANGE: Color = Color {
     r: 0xFF,
     g: 0xB0,
     b: 0x00,
+    a: 0xFF,
 };
 
 pub const ORANGE_75: Color = Color {
     r: 0xCC,
     g: 0x91,
     b: 0x0D,
+    a: 0xFF,
 };
 
 pub const ORANGE_50: Color = Color {
     r: 0x9A,
     g: 0x73,
     b: 0x1B,
+    a: 0xFF,
+};
+
+pub const RED: Color = Color {
+    r: 0xFF,
+    g: 0x33,
+    b: 0x00,
+    a: 0xFF,
+};
+
+pub const RED_75: Color = Color {
+    r: 0xCC,
+    g: 0x33,
+    b: 0x0D,
+    a: 0xFF,
+};
+
+pub const RED_50: Color = Color {
+    r: 0x9A,
+    g: 0x34,
+    b: 0x1B,
+    a: 0xFF,
 };
 
 pub const LIME: Color = Color {
     r: 0x33,
     g: 0xFF,
     b: 0x00,
+    a: 0xFF,
 };
 
 pub const GREEN: Color = Color {
     r: 0x2D,
     g: 0xE0,
     b: 0x00,
+    a: 0xFF,
 };
 
 pub const GREEN_75: Color = Color {
     r: 0x2F,
     g: 0xB5,
     b: 0x0D,
+    a: 0xFF,
 };
 
 pub const GREEN_50: Color = Color {
     r: 0x31,
     g: 0x8B,
     b: 0x1B,
+    a: 0xFF,
 };
 
 pub const WHITE: Color = Color {
     r: 0xF6,
     g: 0xF8,
     b: 0xFF,
+    a: 0xFF,
 };
 
 pub const LIGHT_GREY: Color = Color {
     r: 0xDF,
     g: 0xE3,
     b: 0xED,
+    a: 0xFF,
 };
 
 pub const LIGHT_GREY_75: Color = Color {
     r: 0xB4,
     g: 0xB7,
     b: 0xBF,
+    a: 0xFF,
 };
 
 pub const LIGHT_GREY_50: Color = Color {
     r: 0x8A,
     g: 0x8C,
     b: 0x91,
+    a: 0xFF,
 };
 
 pub const BLACK: Color = Color {
     r: 0x1F,
     g: 0x1F,
     b: 0x1F,
+    a: 0xFF,
 };
 
 pub const GREY: Color = Color {
     r: 0x35,
     g: 0x35,
     b: 0x35,
+    a: 0xFF,
 };
+
+/// Css named colors.
+const CSS_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
@@ -0,0 +1,69 @@
+//! Terminal cursor placement and shape.
+//!
+//! The cursor is hidden while a session is active, the focused element can request it with
+//! [Reactive::get_cursor](crate::tui::Reactive::get_cursor). It is placed after each frame and
+//! hidden again once no element requests it.
+
+use crate::terminal::Viewport;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Look of the cursor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum CursorShape {
+    /// Covers the whole cell.
+    #[default]
+    Block,
+    /// Line below the cell.
+    Underline,
+    /// Vertical line left of the cell.
+    Bar,
+}
+
+/// Cursor requested by an element.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Cursor {
+    /// X position.
+    pub x: u16,
+    /// Y position.
+    pub y: u16,
+    /// Cursor look.
+    pub shape: CursorShape,
+    /// Blink instead of staying steady.
+    pub blinking: bool,
+}
+
+impl Cursor {
+    /// Creates a steady bar cursor at the coordinates, the usual look for text entry.
+    pub fn bar(x: u16, y: u16) -> Cursor {
+        Cursor {
+            x,
+            y,
+            shape: CursorShape::Bar,
+            blinking: false,
+        }
+    }
+
+    /// Returns the escape sequence placing, shaping and showing the cursor.
+    pub fn to_escape(&self, viewport: Viewport) -> String {
+        let shape = match self.shape {
+            CursorShape::Block => 1,
+            CursorShape::Underline => 3,
+            CursorShape::Bar => 5,
+        } + !self.blinking as u8;
+        format!(
+            "{}\x1b[{} q\x1b[?25h",
+            viewport.move_cursor(self.x, self.y),
+            shape
+        )
+    }
+}
+
+/// Escape sequence hiding the cursor.
+pub const HIDE_CURSOR: &str = "\x1b[?25l";
+
+/// Escape sequence restoring the default cursor shape of the terminal.
+pub const RESET_CURSOR_SHAPE: &str = "\x1b[0 q";
@@ -0,0 +1,117 @@
+//! Defines anchored element wrapper.
+
+use std::sync::{Arc, Mutex};
+
+use crate::buffer::Buffer;
+use crate::layout::{Anchor, Rect};
+use crate::tui::{Element, RwLockElement};
+
+/// Tui element that places another element relative to the screen or a reference element, see
+/// [Anchor].
+///
+/// The element is placed each time it is printed, so it follows the screen size and the
+/// reference. The reference should be printed first, its area is the one of the last frame
+/// otherwise. To place elements relative to this one, use the placed element as reference.
+///
+/// Parts placed before the screen are cut off, the element keeps the visible area afterwards
+/// while its own size is remembered for [Extent::Keep](crate::layout::Extent::Keep).
+#[derive(Clone)]
+pub struct Anchored {
+    /// Where the element goes.
+    pub anchor: Anchor,
+    /// Element the anchor is relative to, the screen or the surrounding container if none.
+    pub relative_to: Option<RwLockElement>,
+    /// The placed element, its z position and visibility are used.
+    pub element: RwLockElement,
+    /// Area set on the element by the last print.
+    placed: Arc<Mutex<Option<Placed>>>,
+}
+
+/// Area set on an element together with its size before parts were cut off.
+#[derive(Copy, Clone)]
+struct Placed {
+    /// Visible area.
+    visible: Rect,
+    /// Whole width.
+    width: u16,
+    /// Whole height.
+    height: u16,
+}
+
+impl Anchored {
+    /// Creates a wrapper placing the element relative to the screen.
+    pub fn new(anchor: Anchor, element: RwLockElement) -> Anchored {
+        Anchored {
+            anchor,
+            relative_to: None,
+            element,
+            placed: Arc::default(),
+        }
+    }
+
+    /// Creates a wrapper placing the element relative to the reference element.
+    pub fn relative_to(
+        anchor: Anchor,
+        reference: RwLockElement,
+        element: RwLockElement,
+    ) -> Anchored {
+        Anchored {
+            anchor,
+            relative_to: Some(reference),
+            element,
+            placed: Arc::default(),
+        }
+    }
+}
+
+impl Element for Anchored {
+    fn print(&self, buffer: &mut Buffer) {
+        let reference = match &self.relative_to {
+            Some(reference) => reference.read().unwrap().get_rect().unwrap_or_default(),
+            None => buffer.get_area(),
+        };
+        let mut element = self.element.write().unwrap();
+        let mut placed = self.placed.lock().unwrap();
+        let mut current = element.get_rect().unwrap_or_default();
+        if let Some(last) = *placed {
+            // unchanged since the last print, it was possibly cut
+            if last.visible == current {
+                (current.width, current.height) = (last.width, last.height);
+            }
+        }
+        let (x, y, width, height) = self.anchor.place(reference, current);
+        let visible = self.anchor.resolve(reference, current);
+        // print at the whole size, moved so the cut part ends up before the screen
+        let cut = ((1 - x).max(0) as u16, (1 - y).max(0) as u16);
+        element.set_rect(Rect::new(visible.x, visible.y, width, height));
+        let area = buffer.get_area();
+        let view = Rect::new(
+            1,
+            1,
+            area.right().saturating_sub(1),
+            area.bottom().saturating_sub(1),
+        );
+        buffer.with_scrolled_view(view, cut, |buffer| match element.get_rect() {
+            Some(rect) => buffer.with_clip(rect, |buffer| element.print(buffer)),
+            None => element.print(buffer),
+        });
+        element.set_rect(visible);
+        *placed = Some(Placed {
+            visible,
+            width,
+            height,
+        });
+    }
+
+    fn get_z(&self) -> u16 {
+        self.element.read().unwrap().get_z()
+    }
+
+    fn get_visible(&self) -> bool {
+        self.element.read().unwrap().get_visible()
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.element.write().unwrap().set_visible(visible);
+    }
+}
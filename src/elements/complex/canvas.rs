@@ -1,8 +1,13 @@
 //! Defines canvas and all of its requirements.
 
-use crate::colors::{bg_color_to_string, fg_color_to_string, force_colors, Color};
+use crate::buffer::Buffer;
+use crate::colors::Color;
 use crate::input::{KeyAction, MouseAction};
+use crate::layout::Rect;
+use crate::style::{Attributes, Style};
+use crate::theme::{themed, Role};
 use crate::tui::{self, Reactive};
+use crate::unicode;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock};
@@ -59,11 +64,22 @@ pub struct Element {
     pub fg_color: Option<Color>,
     /// Element background color.
     pub bg_color: Option<Color>,
+    /// Element look attributes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attributes: Attributes,
 }
 
 impl tui::Element for Canvas {
-    fn print(&self) {
-        let mut canvas = vec![vec![" ".to_string(); self.width as usize]; self.height as usize];
+    fn print(&self, buffer: &mut Buffer) {
+        let element_color = themed(self.element_color, Role::Text);
+        for y in 0..self.height {
+            buffer.set_string(
+                self.x,
+                self.y + y,
+                &" ".repeat(self.width as usize),
+                Style::new(element_color, themed(self.bg_color, Role::Surface)),
+            );
+        }
         let mut sorted_elements = self.elements.clone();
         sorted_elements.sort_by(|a, b| {
             let a_z = a.read().unwrap().z;
@@ -71,70 +87,35 @@ impl tui::Element for Canvas {
             a_z.cmp(&b_z)
         });
         for element in sorted_elements {
-            let mut element_grid: Vec<Vec<char>> = Vec::new();
             let element_read = element.read().unwrap();
-            for row in element_read.look.split('\n') {
-                element_grid.push(row.chars().collect());
-            }
-            for y in 0..element_grid.len() {
-                if element_read.y.saturating_add(y as isize) >= 0
-                    && element_read.y.saturating_add(y as isize) < self.height as isize
-                {
-                    for x in 0..element_grid[y].len() {
-                        if element_read.x.saturating_add(x as isize) >= 0
-                            && element_read.x.saturating_add(x as isize) < self.width as isize
-                        {
-                            let field = canvas[element_read.y.saturating_add(y as isize) as usize]
-                                [element_read.x.saturating_add(x as isize) as usize]
-                                .clone();
-                            canvas[element_read.y.saturating_add(y as isize) as usize]
-                                [element_read.x.saturating_add(x as isize) as usize] =
-                                if let Some(color) = element_read.fg_color {
-                                    fg_color_to_string(color)
-                                } else {
-                                    "".to_string()
-                                } + &if let Some(color) = element_read.bg_color {
-                                    bg_color_to_string(color)
-                                } else if field.contains("\x1b[48;") {
-                                    "\x1b[48;".to_string()
-                                        + field
-                                            .split_once("\x1b[48;")
-                                            .unwrap()
-                                            .1
-                                            .split_once(";bm")
-                                            .unwrap()
-                                            .0
-                                        + ";bm"
-                                } else {
-                                    "".to_string()
-                                } + &*element_grid[y][x].to_string();
-                        }
+            for (row_index, row) in element_read.look.split('\n').enumerate() {
+                let y = element_read.y.saturating_add(row_index as isize);
+                if y < 0 || y >= self.height as isize {
+                    continue;
+                }
+                let mut column = 0;
+                for grapheme in unicode::graphemes(row) {
+                    let x = element_read.x.saturating_add(column as isize);
+                    let width = unicode::grapheme_width(grapheme);
+                    column += width;
+                    if width == 0 || x < 0 || x + width as isize > self.width as isize {
+                        continue;
                     }
+                    let cell_x = self.x.saturating_add(x as u16);
+                    let cell_y = self.y.saturating_add(y as u16);
+                    let Some(cell) = buffer.get(cell_x, cell_y) else {
+                        continue;
+                    };
+                    // elements without a background keep the one underneath
+                    let style = Style::new(
+                        element_read.fg_color.or(element_color),
+                        element_read.bg_color.or(cell.style.bg_color),
+                    )
+                    .with_attributes(element_read.attributes);
+                    buffer.set_string(cell_x, cell_y, grapheme, style);
                 }
             }
         }
-        for i in 0..canvas.len() {
-            print!(
-                "\x1b[{};{}H{}{}\x1b[0m",
-                self.y + i as u16,
-                self.x,
-                force_colors(self.element_color, self.bg_color),
-                canvas[i]
-                    .iter()
-                    .map(|e| e.to_string()
-                        + &if let Some(color) = self.element_color {
-                            fg_color_to_string(color)
-                        } else {
-                            "".to_string()
-                        }
-                        + &if let Some(color) = self.bg_color {
-                            bg_color_to_string(color)
-                        } else {
-                            "".to_string()
-                        })
-                    .collect::<String>()
-            )
-        }
     }
 
     fn get_z(&self) -> u16 {
@@ -148,6 +129,17 @@ impl tui::Element for Canvas {
     fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
     }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.x = rect.x;
+        self.y = rect.y;
+        self.width = rect.width;
+        self.height = rect.height;
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        Some(Rect::new(self.x, self.y, self.width, self.height))
+    }
 }
 
 impl Reactive for Canvas {
@@ -0,0 +1,179 @@
+//! Defines container and the handling of its children.
+
+use crate::buffer::Buffer;
+use crate::cursor::Cursor;
+use crate::input::{KeyEvent, MouseEvent};
+use crate::layout::Rect;
+use crate::tui::{print_elements, Element, Reactive, RwLockElement, RwLockReactive};
+
+/// Tui element that owns children positioned relative to its top left corner, 1, 1.
+///
+/// Children are cut off at the bounds of the container. Moving, hiding or disabling the
+/// container does the same to all of its children. Add the container itself to the tui, its
+/// children only to the container.
+#[derive(Clone)]
+pub struct Container {
+    /// X position.
+    pub x: u16,
+    /// Y position.
+    pub y: u16,
+    /// Z position/printing priority.
+    pub z: u16,
+    /// Container width.
+    pub width: u16,
+    /// Container height.
+    pub height: u16,
+    /// Children that are printed.
+    pub elements: Vec<RwLockElement>,
+    /// Children that receive mouse and key events.
+    pub reactive_elements: Vec<RwLockReactive>,
+    /// Index of the child receiving key events, counting enabled children only.
+    pub selected_element: usize,
+    /// Element visibility.
+    pub visible: bool,
+    /// Element selection.
+    pub selected: bool,
+    /// Element functionality.
+    pub enabled: bool,
+}
+
+impl Container {
+    /// Creates an empty, visible and enabled container covering the area.
+    pub fn new(area: Rect) -> Container {
+        Container {
+            x: area.x,
+            y: area.y,
+            z: 0,
+            width: area.width,
+            height: area.height,
+            elements: Vec::new(),
+            reactive_elements: Vec::new(),
+            selected_element: 0,
+            visible: true,
+            selected: false,
+            enabled: true,
+        }
+    }
+
+    /// Returns the selected enabled reactive child, it receives the key events.
+    fn get_focused(&self) -> Option<RwLockReactive> {
+        let enabled: Vec<&RwLockReactive> = self
+            .reactive_elements
+            .iter()
+            .filter(|element| element.read().unwrap().get_enabled())
+            .collect();
+        if enabled.is_empty() {
+            return None;
+        }
+        Some(enabled[self.selected_element % enabled.len()].clone())
+    }
+}
+
+impl Element for Container {
+    fn print(&self, buffer: &mut Buffer) {
+        let area = Rect::new(self.x, self.y, self.width, self.height);
+        buffer.with_view(area, |buffer| {
+            print_elements(buffer, self.elements.clone());
+        });
+    }
+
+    fn get_z(&self) -> u16 {
+        self.z
+    }
+
+    fn get_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.x = rect.x;
+        self.y = rect.y;
+        self.width = rect.width;
+        self.height = rect.height;
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        Some(Rect::new(self.x, self.y, self.width, self.height))
+    }
+}
+
+impl Reactive for Container {
+    /// Forwards the event to the selected child.
+    fn keyboard(&self, data: KeyEvent) {
+        if let Some(element) = self.get_focused() {
+            element.read().unwrap().keyboard(data);
+        }
+    }
+
+    /// Forwards the event to the enabled children under the mouse, relative to their position.
+    fn mouse(&self, data: MouseEvent) {
+        // the event is relative to the container starting at 0, children start at 1
+        let x = data.x as u16 + 1;
+        let y = data.y as u16 + 1;
+        for element in &self.reactive_elements {
+            let element_lock = element.read().unwrap();
+            let rect = Rect::new(
+                element_lock.get_x(),
+                element_lock.get_y(),
+                element_lock.get_width(),
+                element_lock.get_height(),
+            );
+            if element_lock.get_enabled() && rect.contains(x, y) {
+                element_lock.mouse(MouseEvent {
+                    code: data.code,
+                    x: (x - rect.x) as u8,
+                    y: (y - rect.y) as u8,
+                });
+            }
+        }
+    }
+
+    fn get_x(&self) -> u16 {
+        self.x
+    }
+
+    fn get_y(&self) -> u16 {
+        self.y
+    }
+
+    fn get_width(&self) -> u16 {
+        self.width
+    }
+
+    fn get_height(&self) -> u16 {
+        self.height
+    }
+
+    /// Passes the selection on to the selected child.
+    fn set_selected(&mut self, selected: bool) {
+        self.selected = selected;
+        if let Some(element) = self.get_focused() {
+            element.write().unwrap().set_selected(selected);
+        }
+    }
+
+    fn get_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables the container and all reactive children.
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        for element in &self.reactive_elements {
+            element.write().unwrap().set_enabled(enabled);
+        }
+    }
+
+    /// Returns the cursor of the selected child, moved to terminal coordinates.
+    fn get_cursor(&self) -> Option<Cursor> {
+        let cursor = self.get_focused()?.read().unwrap().get_cursor()?;
+        let x = cursor.x.saturating_add(self.x).saturating_sub(1);
+        let y = cursor.y.saturating_add(self.y).saturating_sub(1);
+        let inside = Rect::new(self.x, self.y, self.width, self.height).contains(x, y);
+        inside.then_some(Cursor { x, y, ..cursor })
+    }
+}
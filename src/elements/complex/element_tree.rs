@@ -1,9 +1,17 @@
 //! Defines element tree and all of its requirements.
 
-use crate::{fg_color_to_string, force_colors, tui, Color};
+use crate::{
+    buffer::Buffer,
+    fg_color_to_string,
+    layout::Rect,
+    style::{Attributes, Style},
+    theme::{themed, Role},
+    tui, unicode, Color,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 
 /// Tui element that renders elements in a tree like fashion.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -31,6 +39,9 @@ pub struct ElementTree {
     pub line_color: Option<Color>,
     /// Background color.
     pub bg_color: Option<Color>,
+    /// Element name attributes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attributes: Attributes,
     /// Element visibility.
     pub visible: bool,
 }
@@ -45,14 +56,13 @@ pub struct Icon {
     pub char: char,
 }
 
-impl Icon {
-    /// Returns string representation of the icon.
-    pub fn to_string(&self) -> String {
-        (if let Some(color) = self.color {
-            fg_color_to_string(color)
-        } else {
-            "".to_string()
-        }) + &self.char.to_string()
+impl fmt::Display for Icon {
+    /// Writes the escaped representation of the icon.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(color) = self.color {
+            write!(f, "{}", fg_color_to_string(color))?;
+        }
+        write!(f, "{}", self.char)
     }
 }
 
@@ -114,15 +124,14 @@ pub struct Folder {
     pub children: Vec<Part>,
 }
 
+/// One expanded row of the element tree: line prefix, icon, buffer and name.
+type Row = (String, Option<Icon>, String, String);
+
 impl Folder {
-    /// Returns a vector of strings representing the structure of nested elements.
-    fn expand(
-        &self,
-        line_set: &LineSet,
-        icon_map: &HashMap<String, Icon>,
-    ) -> Vec<(String, String, String, String)> {
-        let mut rows: Vec<(String, String, String, String)> = Vec::new();
-        for i in 0..self.children.len() {
+    /// Returns a vector of rows representing the structure of nested elements.
+    fn expand(&self, line_set: &LineSet, icon_map: &HashMap<String, Icon>) -> Vec<Row> {
+        let mut rows: Vec<Row> = Vec::new();
+        for (i, child) in self.children.iter().enumerate() {
             rows.push((
                 if i == 0 && i == self.children.len() - 1 {
                     &line_set.top_and_only
@@ -134,26 +143,24 @@ impl Folder {
                     &line_set.middle_entry
                 }
                 .to_string(),
-                if let Some(icon) = match self.children.get(i.clone()).unwrap() {
+                match child {
                     Part::Element(element) => icon_map.get(&*element.icon_id),
                     Part::Folder(folder) => icon_map.get(if folder.is_open {
                         &*folder.icon_open_id
                     } else {
                         &*folder.icon_closed_id
                     }),
-                } {
-                    icon.to_string()
-                } else {
-                    "".to_string()
-                },
+                }
+                .copied(),
                 line_set.horizontal_buffer.to_string(),
-                match self.children.get(i.clone()).unwrap() {
+                match child {
                     Part::Element(element) => element.name.clone(),
                     Part::Folder(folder) => folder.name.clone(),
                 },
             ));
-            match self.children.get(i.clone()).unwrap() {
+            match child {
                 Part::Element(_) => {}
+                Part::Folder(folder) if !folder.is_open => {}
                 Part::Folder(folder) => {
                     rows.extend(folder.expand(line_set, icon_map).iter().map(|e| {
                         (
@@ -162,7 +169,7 @@ impl Folder {
                             } else {
                                 line_set.vertical_buffer.to_string()
                             } + &e.0.clone(),
-                            e.1.clone(),
+                            e.1,
                             e.2.clone(),
                             e.3.clone(),
                         )
@@ -175,64 +182,67 @@ impl Folder {
 }
 
 impl tui::Element for ElementTree {
-    fn print(&self) {
-        // todo make it actually work
+    fn print(&self, buffer: &mut Buffer) {
         let lines = self.elements.expand(&self.line_set, &self.icon_map);
+        let line_color = themed(self.line_color, Role::Border);
+        let bg_color = themed(self.bg_color, Role::Surface);
+        let line_style = Style::new(line_color, bg_color);
+        let element_style = Style::new(themed(self.element_color, Role::Text), bg_color)
+            .with_attributes(self.attributes);
         for i in 0..self.height {
-            let mut line = force_colors(None, self.bg_color);
-            let mut line_parts = lines
-                .get(i as usize)
-                .unwrap_or(&(String::new(), String::new(), String::new(), String::new()))
-                .clone();
-            if line_parts.0.len() > 0 {
-                if i == self.height - 1 {
-                    line_parts = (
-                        line_parts
-                            .0
-                            .replace(
-                                self.line_set.vertical_buffer,
-                                &self.line_set.vertical_continuation.to_string(),
-                            )
-                            .replace(
-                                self.line_set.middle_entry,
-                                &self.line_set.vertical_continuation.to_string(),
-                            ),
-                        String::new(),
-                        String::new(),
-                        String::new(),
+            let y = self.y + i;
+            buffer.set_string(
+                self.x,
+                y,
+                &" ".repeat(self.width as usize),
+                Style::new(None, bg_color),
+            );
+            let Some(line_parts) = lines.get(i as usize) else {
+                continue;
+            };
+            if line_parts.0.is_empty() {
+                continue;
+            }
+            // indicate rows that don't fit anymore
+            if i == self.height - 1 && lines.len() > self.height as usize {
+                let continuation = line_parts
+                    .0
+                    .replace(
+                        self.line_set.vertical_buffer,
+                        &self.line_set.vertical_continuation.to_string(),
+                    )
+                    .replace(
+                        self.line_set.middle_entry,
+                        &self.line_set.vertical_continuation.to_string(),
                     );
-                    line = force_colors(self.line_color, self.bg_color) + &line_parts.0 + "  ";
-                } else {
-                    if line_parts.0.chars().count() + 2 + line_parts.3.chars().count()
-                        > self.width as usize
-                    {
-                        line_parts.3.truncate(
-                            (self.width as usize).saturating_sub(line_parts.0.chars().count() + 3),
-                        );
-                        line_parts.3 += &self.line_set.horizontal_continuation.to_string();
-                    }
-                    line = force_colors(self.line_color, self.bg_color)
-                        + &line_parts.0
-                        + &line_parts.1
-                        + &force_colors(self.line_color, self.bg_color)
-                        + &line_parts.2
-                        + &force_colors(self.element_color, self.bg_color)
-                        + &line_parts.3
-                }
+                buffer.set_string(self.x, y, &continuation, line_style);
+                continue;
             }
-            print!(
-                "\x1b[{};{}H{}{}\x1b[0m",
-                self.y + i,
-                self.x,
-                line,
-                " ".repeat(
-                    (self.width as usize).saturating_sub(if line_parts.0.len() > 0 {
-                        line_parts.0.chars().count() + 2 + line_parts.3.chars().count()
-                    } else {
-                        0
-                    })
+            let mut name = line_parts.3.clone();
+            let prefix_width = unicode::width(&line_parts.0);
+            if prefix_width + 2 + unicode::width(&name) > self.width as usize {
+                name = unicode::truncate(
+                    &name,
+                    (self.width as usize).saturating_sub(prefix_width + 3),
                 )
-            )
+                .to_string();
+                name += &self.line_set.horizontal_continuation.to_string();
+            }
+            let mut x = self.x;
+            x += buffer.set_string(x, y, &line_parts.0, line_style);
+            if let Some(icon) = line_parts.1 {
+                x += buffer.set_string(
+                    x,
+                    y,
+                    &icon.char.to_string(),
+                    Style::new(
+                        themed(icon.color.or(self.line_color), Role::Accent),
+                        bg_color,
+                    ),
+                );
+            }
+            x += buffer.set_string(x, y, &line_parts.2, line_style);
+            buffer.set_string(x, y, &name, element_style);
         }
     }
 
@@ -247,6 +257,17 @@ impl tui::Element for ElementTree {
     fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
     }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.x = rect.x;
+        self.y = rect.y;
+        self.width = rect.width;
+        self.height = rect.height;
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        Some(Rect::new(self.x, self.y, self.width, self.height))
+    }
 }
 
 #[allow(missing_docs)]
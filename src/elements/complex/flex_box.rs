@@ -0,0 +1,95 @@
+//! Defines flex box and its children.
+
+use crate::buffer::Buffer;
+use crate::layout::{Constraint, Flex, Rect};
+use crate::tui::{print_elements, Element, RwLockElement};
+
+/// Tui element that places its children in a row or column, see [Flex].
+///
+/// The children are laid out each time the box is printed, so they follow the screen size.
+#[derive(Clone)]
+pub struct FlexBox {
+    /// Z position/printing priority.
+    pub z: u16,
+    /// Area the children are placed in, the screen or the surrounding container if none.
+    pub area: Option<Rect>,
+    /// Layout of the children.
+    pub flex: Flex,
+    /// Children in layout order.
+    pub children: Vec<FlexChild>,
+    /// Element visibility.
+    pub visible: bool,
+}
+
+/// Element placed by a [FlexBox].
+#[derive(Clone)]
+pub struct FlexChild {
+    /// Size along the direction of the box.
+    pub constraint: Constraint,
+    /// The placed element.
+    pub element: RwLockElement,
+}
+
+impl FlexBox {
+    /// Creates a visible box filling the screen.
+    pub fn new(flex: Flex) -> FlexBox {
+        FlexBox {
+            z: 0,
+            area: None,
+            flex,
+            children: Vec::new(),
+            visible: true,
+        }
+    }
+
+    /// Adds a child at the end.
+    pub fn push(&mut self, constraint: Constraint, element: RwLockElement) {
+        self.children.push(FlexChild {
+            constraint,
+            element,
+        });
+    }
+
+    /// Assigns each child its area within the given one.
+    pub fn layout(&self, area: Rect) {
+        let constraints: Vec<Constraint> =
+            self.children.iter().map(|child| child.constraint).collect();
+        let rects = self.flex.split(area, &constraints);
+        for (child, rect) in self.children.iter().zip(rects) {
+            child.element.write().unwrap().set_rect(rect);
+        }
+    }
+}
+
+impl Element for FlexBox {
+    fn print(&self, buffer: &mut Buffer) {
+        let area = self.area.unwrap_or(buffer.get_area());
+        self.layout(area);
+        let elements = self
+            .children
+            .iter()
+            .map(|child| child.element.clone())
+            .collect();
+        print_elements(buffer, elements);
+    }
+
+    fn get_z(&self) -> u16 {
+        self.z
+    }
+
+    fn get_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.area = Some(rect);
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        self.area
+    }
+}
@@ -0,0 +1,112 @@
+//! Defines grid box and its children.
+
+use crate::buffer::Buffer;
+use crate::layout::{Grid, GridPlacement, Rect};
+use crate::tui::{print_elements, Element, RwLockElement};
+
+/// Tui element that places its children in the cells of a [Grid].
+///
+/// The children are laid out each time the box is printed, so they follow the screen size.
+/// Children outside of the grid are not printed.
+#[derive(Clone)]
+pub struct GridBox {
+    /// Z position/printing priority.
+    pub z: u16,
+    /// Area the grid covers, the screen or the surrounding container if none.
+    pub area: Option<Rect>,
+    /// Tracks and areas of the grid.
+    pub grid: Grid,
+    /// Placed children.
+    pub children: Vec<GridChild>,
+    /// Element visibility.
+    pub visible: bool,
+}
+
+/// Element placed by a [GridBox].
+#[derive(Clone)]
+pub struct GridChild {
+    /// Cells the element covers.
+    pub placement: GridPlacement,
+    /// Width and height used for auto tracks.
+    pub content_size: (u16, u16),
+    /// The placed element.
+    pub element: RwLockElement,
+}
+
+impl GridBox {
+    /// Creates a visible grid filling the screen.
+    pub fn new(grid: Grid) -> GridBox {
+        GridBox {
+            z: 0,
+            area: None,
+            grid,
+            children: Vec::new(),
+            visible: true,
+        }
+    }
+
+    /// Adds a child, auto tracks use the size it has now.
+    pub fn push(&mut self, placement: GridPlacement, element: RwLockElement) {
+        let content_size = element
+            .read()
+            .unwrap()
+            .get_rect()
+            .map_or((0, 0), |rect| (rect.width, rect.height));
+        self.children.push(GridChild {
+            placement,
+            content_size,
+            element,
+        });
+    }
+
+    /// Assigns each child its area within the given one, returns the children inside the grid.
+    pub fn layout(&self, area: Rect) -> Vec<RwLockElement> {
+        let placements: Vec<GridPlacement> = self
+            .children
+            .iter()
+            .map(|child| child.placement.clone())
+            .collect();
+        let content_sizes: Vec<(u16, u16)> = self
+            .children
+            .iter()
+            .map(|child| child.content_size)
+            .collect();
+        let rects = self.grid.place(area, &placements, &content_sizes);
+        self.children
+            .iter()
+            .zip(rects)
+            .filter_map(|(child, rect)| {
+                child.element.write().unwrap().set_rect(rect?);
+                Some(child.element.clone())
+            })
+            .collect()
+    }
+}
+
+impl Element for GridBox {
+    fn print(&self, buffer: &mut Buffer) {
+        let area = self.area.unwrap_or(buffer.get_area());
+        let elements = self.layout(area);
+        print_elements(buffer, elements);
+    }
+
+    fn get_z(&self) -> u16 {
+        self.z
+    }
+
+    fn get_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.area = Some(rect);
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        self.area
+    }
+}
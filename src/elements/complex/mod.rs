@@ -1,7 +1,17 @@
 //! Contains more complex elements.
 
+pub mod anchored;
 pub mod canvas;
+pub mod container;
 pub mod element_tree;
+pub mod flex_box;
+pub mod grid_box;
+pub mod scroll_view;
 
+pub use anchored::Anchored;
 pub use canvas::Canvas;
+pub use container::Container;
 pub use element_tree::ElementTree;
+pub use flex_box::FlexBox;
+pub use grid_box::GridBox;
+pub use scroll_view::ScrollView;
@@ -0,0 +1,575 @@
+//! Defines scroll view and its scroll bars.
+
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::buffer::Buffer;
+use crate::colors::Color;
+use crate::cursor::Cursor;
+use crate::input::callbacks::{
+    APPLICATION_SEQUENCE, ARROW_DOWN, ARROW_LEFT, ARROW_RIGHT, ARROW_UP, CONTROL_SEQUENCE, ESCAPE,
+    MOUSE_LEFT_DRAG, MOUSE_LEFT_PRESS, MOUSE_RELEASE, MOUSE_WHEEL_DOWN, MOUSE_WHEEL_UP, PAGE_DOWN,
+    PAGE_UP, SEQUENCE_END,
+};
+use crate::input::observers::{get_mouse_generation, SEQUENCE_TIMEOUT};
+use crate::input::{KeyEvent, MouseEvent};
+use crate::layout::Rect;
+use crate::style::Style;
+use crate::theme::{themed, Role};
+use crate::tui::{print_elements, Element, Reactive, RwLockElement, RwLockReactive};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Tui element that shows part of children larger than its area and scrolls through them.
+///
+/// Children are positioned relative to the top left corner of the content, 1, 1. The content
+/// size is the area covered by all children with a size, see [Element::get_rect]. Scroll bars
+/// are shown on the right and bottom edge when the content does not fit.
+///
+/// Scrolls with the mouse wheel, the arrow and page keys and by dragging the scroll bars. Drags
+/// only continue while the mouse is above the scroll view. Other key events go to the first
+/// enabled child, other mouse events to the children under the mouse. A lone escape key reaches
+/// the child together with the next key if that came too late to continue a sequence.
+pub struct ScrollView {
+    /// X position.
+    pub x: u16,
+    /// Y position.
+    pub y: u16,
+    /// Z position/printing priority.
+    pub z: u16,
+    /// Width including the scroll bars.
+    pub width: u16,
+    /// Height including the scroll bars.
+    pub height: u16,
+    /// Children that are printed.
+    pub elements: Vec<RwLockElement>,
+    /// Children that receive mouse and key events.
+    pub reactive_elements: Vec<RwLockReactive>,
+    /// Characters the scroll bars are drawn with.
+    pub bar_set: ScrollBarSet,
+    /// Scroll bar track color.
+    pub bar_color: Option<Color>,
+    /// Scroll bar thumb color.
+    pub thumb_color: Option<Color>,
+    /// Lines scrolled per mouse wheel step.
+    pub scroll_step: u16,
+    /// Element visibility.
+    pub visible: bool,
+    /// Element selection.
+    pub selected: bool,
+    /// Element functionality.
+    pub enabled: bool,
+    /// Scroll offset and input in progress.
+    state: Mutex<ScrollState>,
+}
+
+/// Defines the characters of the scroll bars.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone)]
+pub struct ScrollBarSet {
+    /// Character for the vertical bar outside of the thumb.
+    pub vertical_track: char,
+    /// Character for the vertical thumb, the visible part of the content.
+    pub vertical_thumb: char,
+    /// Character for the horizontal bar outside of the thumb.
+    pub horizontal_track: char,
+    /// Character for the horizontal thumb, the visible part of the content.
+    pub horizontal_thumb: char,
+    /// Character for the cell between both bars.
+    pub corner: char,
+}
+
+pub const SCROLL_BARS_LIGHT: ScrollBarSet = ScrollBarSet {
+    vertical_track: '│',
+    vertical_thumb: '┃',
+    horizontal_track: '─',
+    horizontal_thumb: '━',
+    corner: ' ',
+};
+
+pub const SCROLL_BARS_BLOCK: ScrollBarSet = ScrollBarSet {
+    vertical_track: '░',
+    vertical_thumb: '█',
+    horizontal_track: '░',
+    horizontal_thumb: '█',
+    corner: ' ',
+};
+
+/// Scroll offset and input in progress.
+#[derive(Default)]
+struct ScrollState {
+    /// Columns scrolled to the right.
+    x: u16,
+    /// Lines scrolled down.
+    y: u16,
+    /// Scroll bar being dragged.
+    drag: Option<Drag>,
+    /// Bytes of an unfinished escape sequence.
+    sequence: Vec<u8>,
+    /// Time the unfinished sequence started.
+    sequence_start: Option<Instant>,
+    /// Mouse generation when the unfinished sequence started, see [get_mouse_generation].
+    mouse_generation: usize,
+}
+
+impl ScrollState {
+    /// Removes an unfinished sequence that timed out or turned out to be the start of a mouse
+    /// event, returns the keys that still have to be forwarded.
+    fn take_stale_sequence(&mut self) -> Vec<u8> {
+        if self.sequence.is_empty() {
+            Vec::new()
+        } else if self.mouse_generation != get_mouse_generation() {
+            // the mouse event itself went to another element
+            self.sequence.clear();
+            Vec::new()
+        } else if self
+            .sequence_start
+            .is_some_and(|start| start.elapsed() >= SEQUENCE_TIMEOUT)
+        {
+            std::mem::take(&mut self.sequence)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Scroll bar being dragged.
+#[derive(Copy, Clone)]
+struct Drag {
+    /// Dragging the vertical bar, the horizontal one otherwise.
+    vertical: bool,
+    /// Cells between the start of the thumb and the mouse.
+    grab: u16,
+}
+
+/// Visible part of the scroll view at its current size.
+struct Layout {
+    /// Area showing the content.
+    viewport: Rect,
+    /// Content width and height.
+    content: (u16, u16),
+    /// Vertical bar shown.
+    vertical: bool,
+    /// Horizontal bar shown.
+    horizontal: bool,
+}
+
+impl Layout {
+    /// Returns the scroll offset limited to the content.
+    fn clamp(&self, x: i32, y: i32) -> (u16, u16) {
+        let max_x = self.content.0.saturating_sub(self.viewport.width);
+        let max_y = self.content.1.saturating_sub(self.viewport.height);
+        (
+            x.clamp(0, max_x as i32) as u16,
+            y.clamp(0, max_y as i32) as u16,
+        )
+    }
+}
+
+impl ScrollView {
+    /// Creates an empty, visible and enabled scroll view covering the area.
+    pub fn new(area: Rect) -> ScrollView {
+        ScrollView {
+            x: area.x,
+            y: area.y,
+            z: 0,
+            width: area.width,
+            height: area.height,
+            elements: Vec::new(),
+            reactive_elements: Vec::new(),
+            bar_set: SCROLL_BARS_LIGHT,
+            bar_color: None,
+            thumb_color: None,
+            scroll_step: 3,
+            visible: true,
+            selected: false,
+            enabled: true,
+            state: Mutex::new(ScrollState::default()),
+        }
+    }
+
+    /// Returns the width and height covered by the children.
+    pub fn get_content_size(&self) -> (u16, u16) {
+        self.elements
+            .iter()
+            .filter_map(|element| element.read().unwrap().get_rect())
+            .fold((0, 0), |(width, height), rect| {
+                (
+                    width.max(rect.right().saturating_sub(1)),
+                    height.max(rect.bottom().saturating_sub(1)),
+                )
+            })
+    }
+
+    /// Returns the columns scrolled to the right and the lines scrolled down.
+    pub fn get_scroll(&self) -> (u16, u16) {
+        let state = self.state.lock().unwrap();
+        self.get_layout().clamp(state.x as i32, state.y as i32)
+    }
+
+    /// Scrolls so the given column and line of the content are in the top left corner, as far
+    /// as the content allows.
+    pub fn scroll_to(&self, x: u16, y: u16) {
+        let layout = self.get_layout();
+        let mut state = self.state.lock().unwrap();
+        (state.x, state.y) = layout.clamp(x as i32 - 1, y as i32 - 1);
+    }
+
+    /// Scrolls by the given columns and lines, negative values scroll left and up.
+    pub fn scroll_by(&self, x: i32, y: i32) {
+        let layout = self.get_layout();
+        let mut state = self.state.lock().unwrap();
+        (state.x, state.y) = layout.clamp(state.x as i32 + x, state.y as i32 + y);
+    }
+
+    /// Returns the viewport and the scroll bars needed to show the content.
+    fn get_layout(&self) -> Layout {
+        let content = self.get_content_size();
+        let (mut vertical, mut horizontal) = (false, false);
+        // a bar takes space from the other direction, which can require the other bar
+        for _ in 0..2 {
+            vertical = content.1 > self.height.saturating_sub(horizontal as u16);
+            horizontal = content.0 > self.width.saturating_sub(vertical as u16);
+        }
+        Layout {
+            viewport: Rect::new(
+                self.x,
+                self.y,
+                self.width.saturating_sub(vertical as u16),
+                self.height.saturating_sub(horizontal as u16),
+            ),
+            content,
+            vertical,
+            horizontal,
+        }
+    }
+
+    /// Passes the keys on to the focused child.
+    fn forward_keys(&self, codes: Vec<u8>) {
+        if codes.is_empty() {
+            return;
+        }
+        if let Some(element) = self.get_focused() {
+            for code in codes {
+                element.read().unwrap().keyboard(KeyEvent { code });
+            }
+        }
+    }
+
+    /// Returns the first enabled reactive child, it receives the key events.
+    fn get_focused(&self) -> Option<RwLockReactive> {
+        self.reactive_elements
+            .iter()
+            .find(|element| element.read().unwrap().get_enabled())
+            .cloned()
+    }
+}
+
+/// Scrolls so the thumb of the dragged bar starts at the mouse less the grab offset.
+fn drag_to(state: &mut ScrollState, layout: &Layout, drag: Drag, position: u16) {
+    let position = position.saturating_sub(drag.grab);
+    if drag.vertical {
+        let track = layout.viewport.height;
+        let y = scroll_for(track, track, layout.content.1, position);
+        (state.x, state.y) = layout.clamp(state.x as i32, y as i32);
+    } else {
+        let track = layout.viewport.width;
+        let x = scroll_for(track, track, layout.content.0, position);
+        (state.x, state.y) = layout.clamp(x as i32, state.y as i32);
+    }
+}
+
+/// Returns the start and length of the thumb on a track, relative to the track.
+fn thumb(track: u16, view: u16, content: u16, scroll: u16) -> (u16, u16) {
+    let max_scroll = content.saturating_sub(view);
+    if track == 0 || max_scroll == 0 {
+        return (0, track);
+    }
+    let length = (track as u32 * view as u32 / content as u32).clamp(1, track as u32) as u16;
+    let free = (track - length) as u32;
+    let start = (free * scroll as u32 + max_scroll as u32 / 2) / max_scroll as u32;
+    (start.min(free) as u16, length)
+}
+
+/// Returns the scroll offset that puts the start of the thumb at the position on the track.
+fn scroll_for(track: u16, view: u16, content: u16, position: u16) -> u16 {
+    let (_, length) = thumb(track, view, content, 0);
+    let free = track.saturating_sub(length) as u32;
+    if free == 0 {
+        return 0;
+    }
+    let max_scroll = content.saturating_sub(view) as u32;
+    (((position as u32).min(free) * max_scroll + free / 2) / free) as u16
+}
+
+/// Returns the cells between the start of the thumb and the mouse, the middle of the thumb if
+/// the mouse is outside of it, so the thumb jumps there.
+fn grab(start: u16, length: u16, position: u16) -> u16 {
+    if (start..start + length).contains(&position) {
+        position - start
+    } else {
+        length / 2
+    }
+}
+
+impl Element for ScrollView {
+    fn print(&self, buffer: &mut Buffer) {
+        let layout = self.get_layout();
+        let (scroll_x, scroll_y) = self.get_scroll();
+        let viewport = layout.viewport;
+        buffer.with_scrolled_view(viewport, (scroll_x, scroll_y), |buffer| {
+            print_elements(buffer, self.elements.clone());
+        });
+
+        let track_style = Style::new(themed(self.bar_color, Role::Border), None);
+        let thumb_style = Style::new(themed(self.thumb_color, Role::Accent), None);
+        if layout.vertical {
+            let (start, length) =
+                thumb(viewport.height, viewport.height, layout.content.1, scroll_y);
+            for i in 0..viewport.height {
+                if (start..start + length).contains(&i) {
+                    buffer.set(
+                        viewport.right(),
+                        viewport.y + i,
+                        self.bar_set.vertical_thumb,
+                        thumb_style,
+                    );
+                } else {
+                    buffer.set(
+                        viewport.right(),
+                        viewport.y + i,
+                        self.bar_set.vertical_track,
+                        track_style,
+                    );
+                }
+            }
+        }
+        if layout.horizontal {
+            let (start, length) = thumb(viewport.width, viewport.width, layout.content.0, scroll_x);
+            for i in 0..viewport.width {
+                if (start..start + length).contains(&i) {
+                    buffer.set(
+                        viewport.x + i,
+                        viewport.bottom(),
+                        self.bar_set.horizontal_thumb,
+                        thumb_style,
+                    );
+                } else {
+                    buffer.set(
+                        viewport.x + i,
+                        viewport.bottom(),
+                        self.bar_set.horizontal_track,
+                        track_style,
+                    );
+                }
+            }
+        }
+        if layout.vertical && layout.horizontal {
+            buffer.set(
+                viewport.right(),
+                viewport.bottom(),
+                self.bar_set.corner,
+                track_style,
+            );
+        }
+    }
+
+    fn get_z(&self) -> u16 {
+        self.z
+    }
+
+    fn get_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.x = rect.x;
+        self.y = rect.y;
+        self.width = rect.width;
+        self.height = rect.height;
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        Some(Rect::new(self.x, self.y, self.width, self.height))
+    }
+}
+
+impl Reactive for ScrollView {
+    /// Scrolls on arrow and page keys, forwards other keys to the first enabled child.
+    fn keyboard(&self, data: KeyEvent) {
+        let layout = self.get_layout();
+        let page = layout.viewport.height as i32;
+        let mut state = self.state.lock().unwrap();
+        let stale = state.take_stale_sequence();
+        if state.sequence.is_empty() {
+            state.sequence_start = Some(Instant::now());
+            state.mouse_generation = get_mouse_generation();
+        }
+        state.sequence.push(data.code);
+        let delta = match state.sequence[..] {
+            [ESCAPE]
+            | [ESCAPE, CONTROL_SEQUENCE | APPLICATION_SEQUENCE]
+            | [ESCAPE, CONTROL_SEQUENCE, PAGE_UP | PAGE_DOWN] => {
+                drop(state);
+                self.forward_keys(stale);
+                return;
+            }
+            [ESCAPE, CONTROL_SEQUENCE | APPLICATION_SEQUENCE, ARROW_UP] => Some((0, -1)),
+            [ESCAPE, CONTROL_SEQUENCE | APPLICATION_SEQUENCE, ARROW_DOWN] => Some((0, 1)),
+            [ESCAPE, CONTROL_SEQUENCE | APPLICATION_SEQUENCE, ARROW_RIGHT] => Some((1, 0)),
+            [ESCAPE, CONTROL_SEQUENCE | APPLICATION_SEQUENCE, ARROW_LEFT] => Some((-1, 0)),
+            [ESCAPE, CONTROL_SEQUENCE, PAGE_UP, SEQUENCE_END] => Some((0, -page)),
+            [ESCAPE, CONTROL_SEQUENCE, PAGE_DOWN, SEQUENCE_END] => Some((0, page)),
+            _ => None,
+        };
+        let sequence = std::mem::take(&mut state.sequence);
+        match delta {
+            Some((x, y)) => {
+                (state.x, state.y) = layout.clamp(state.x as i32 + x, state.y as i32 + y);
+                drop(state);
+                self.forward_keys(stale);
+            }
+            None => {
+                drop(state);
+                self.forward_keys(stale);
+                self.forward_keys(sequence);
+            }
+        }
+    }
+
+    /// Scrolls on wheel events and scroll bar drags, forwards other events to the enabled
+    /// children under the mouse, relative to their position.
+    fn mouse(&self, data: MouseEvent) {
+        let layout = self.get_layout();
+        let viewport = layout.viewport;
+        // the event is relative to the scroll view starting at 0
+        let x = data.x as u16;
+        let y = data.y as u16;
+        let mut state = self.state.lock().unwrap();
+        // the start of mouse events also reaches the key events
+        state.sequence.clear();
+        let step = self.scroll_step as i32;
+        match data.code {
+            MOUSE_WHEEL_UP => {
+                (state.x, state.y) = layout.clamp(state.x as i32, state.y as i32 - step);
+                return;
+            }
+            MOUSE_WHEEL_DOWN => {
+                (state.x, state.y) = layout.clamp(state.x as i32, state.y as i32 + step);
+                return;
+            }
+            MOUSE_LEFT_PRESS if layout.vertical && x == viewport.width && y < viewport.height => {
+                let (_, scroll_y) = layout.clamp(state.x as i32, state.y as i32);
+                let (start, length) =
+                    thumb(viewport.height, viewport.height, layout.content.1, scroll_y);
+                let drag = Drag {
+                    vertical: true,
+                    grab: grab(start, length, y),
+                };
+                state.drag = Some(drag);
+                drag_to(&mut state, &layout, drag, y);
+                return;
+            }
+            MOUSE_LEFT_PRESS if layout.horizontal && y == viewport.height && x < viewport.width => {
+                let (scroll_x, _) = layout.clamp(state.x as i32, state.y as i32);
+                let (start, length) =
+                    thumb(viewport.width, viewport.width, layout.content.0, scroll_x);
+                let drag = Drag {
+                    vertical: false,
+                    grab: grab(start, length, x),
+                };
+                state.drag = Some(drag);
+                drag_to(&mut state, &layout, drag, x);
+                return;
+            }
+            MOUSE_LEFT_DRAG if state.drag.is_some() => {
+                let drag = state.drag.unwrap();
+                let position = if drag.vertical { y } else { x };
+                drag_to(&mut state, &layout, drag, position);
+                return;
+            }
+            MOUSE_RELEASE if state.drag.is_some() => {
+                state.drag = None;
+                return;
+            }
+            _ => {}
+        }
+        if x >= viewport.width || y >= viewport.height {
+            return;
+        }
+        let (scroll_x, scroll_y) = layout.clamp(state.x as i32, state.y as i32);
+        drop(state);
+
+        // children start at 1 in the top left corner of the content
+        let x = x + 1 + scroll_x;
+        let y = y + 1 + scroll_y;
+        for element in &self.reactive_elements {
+            let element_lock = element.read().unwrap();
+            let rect = Rect::new(
+                element_lock.get_x(),
+                element_lock.get_y(),
+                element_lock.get_width(),
+                element_lock.get_height(),
+            );
+            if element_lock.get_enabled() && rect.contains(x, y) {
+                element_lock.mouse(MouseEvent {
+                    code: data.code,
+                    x: (x - rect.x) as u8,
+                    y: (y - rect.y) as u8,
+                });
+            }
+        }
+    }
+
+    fn get_x(&self) -> u16 {
+        self.x
+    }
+
+    fn get_y(&self) -> u16 {
+        self.y
+    }
+
+    fn get_width(&self) -> u16 {
+        self.width
+    }
+
+    fn get_height(&self) -> u16 {
+        self.height
+    }
+
+    /// Selects the first enabled child.
+    fn set_selected(&mut self, selected: bool) {
+        self.selected = selected;
+        if let Some(element) = self.get_focused() {
+            element.write().unwrap().set_selected(selected);
+        }
+    }
+
+    fn get_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables the scroll view and all reactive children.
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        for element in &self.reactive_elements {
+            element.write().unwrap().set_enabled(enabled);
+        }
+    }
+
+    /// Returns the cursor of the first enabled child, moved to terminal coordinates, none if it
+    /// is scrolled out of view.
+    fn get_cursor(&self) -> Option<Cursor> {
+        let cursor = self.get_focused()?.read().unwrap().get_cursor()?;
+        let (scroll_x, scroll_y) = self.get_scroll();
+        let x = (cursor.x as i32 + self.x as i32 - 1 - scroll_x as i32).max(0) as u16;
+        let y = (cursor.y as i32 + self.y as i32 - 1 - scroll_y as i32).max(0) as u16;
+        let inside = self.get_layout().viewport.contains(x, y);
+        inside.then_some(Cursor { x, y, ..cursor })
+    }
+}
@@ -1,77 +1,146 @@
 //! Contains premade elements.
 
-use crate::colors::{force_colors, Color};
+use crate::buffer::Buffer;
+use crate::rich_text::{to_lines, Line};
+use crate::style::Style;
+use std::collections::VecDeque;
 
 pub mod complex;
 pub mod simple;
 
 /// Prints content at the coordinates, splits into multiple lines at '\n'
-pub fn print(x: u16, y: u16, fg_color: Option<Color>, bg_color: Option<Color>, text: String) {
-    let lines: Vec<&str> = text.split('\n').collect();
-    for i in 0..lines.len() {
-        print!(
-            "\x1b[{};{}H{}{}\x1b[0m",
-            y + i as u16,
-            x,
-            force_colors(fg_color, bg_color),
-            lines.get(i).unwrap_or(&"")
+pub fn print(buffer: &mut Buffer, x: u16, y: u16, style: Style, text: String) {
+    print_lines(buffer, x, y, style, &to_lines(&text, false));
+}
+
+/// Prints styled lines at the coordinates, span styles are applied on top of the style.
+pub fn print_lines(buffer: &mut Buffer, x: u16, y: u16, style: Style, lines: &[Line]) {
+    for (i, line) in lines.iter().enumerate() {
+        print_line(buffer, x, y + i as u16, style, line);
+    }
+}
+
+/// Prints one styled line, returns the number of cells used.
+fn print_line(buffer: &mut Buffer, x: u16, y: u16, style: Style, line: &Line) -> u16 {
+    let mut used = 0;
+    for span in &line.spans {
+        used += buffer.set_string(
+            x.saturating_add(used),
+            y,
+            &span.text,
+            style.patch(span.style),
         );
     }
+    used
+}
+
+/// Splits the string contents into lines fitting the width, ignoring '\n'.
+/// Wraps at ' ' or if unable in the middle of words.
+pub fn wrap(text: &str, width: u16) -> Vec<String> {
+    wrap_lines(&[Line::from(text)], width)
+        .iter()
+        .map(Line::get_plain)
+        .collect()
+}
+
+/// Splits styled lines into lines fitting the width, ignoring line breaks.
+/// Wraps at ' ' or if unable in the middle of words, keeping the span styles.
+pub fn wrap_lines(lines: &[Line], width: u16) -> Vec<Line> {
+    let width = width as usize;
+    // words with the style of the space in front of them
+    let mut words: VecDeque<(Style, Line)> = VecDeque::from([Default::default()]);
+    for span in lines.iter().flat_map(|line| &line.spans) {
+        for (i, part) in span.text.replace('\n', "").split(' ').enumerate() {
+            if i > 0 {
+                words.push_back((span.style, Line::default()));
+            }
+            words.back_mut().unwrap().1.push(part, span.style);
+        }
+    }
+    let mut lines = Vec::new();
+    if width == 0 {
+        return lines;
+    }
+    while !words.is_empty() {
+        let mut line = Line::default();
+        while let Some((space_style, word)) = words.front() {
+            let word_width = word.get_width();
+            if line.spans.is_empty() {
+                if word_width <= width {
+                    line = words.pop_front().unwrap().1;
+                    continue;
+                }
+                let (space_style, word) = words.pop_front().unwrap();
+                let (start, rest) = word.split_at_width(width);
+                let (mut start, mut rest) = if start.get_plain().ends_with('-') {
+                    (start, rest)
+                } else {
+                    let (mut start, rest) = word.split_at_width(width - 1);
+                    if let Some(span) = start.spans.last() {
+                        let style = span.style;
+                        start.push("-", style);
+                    }
+                    (start, rest)
+                };
+                if start.spans.is_empty() {
+                    // not even one grapheme fits, still make progress
+                    (start, rest) = word.split_first();
+                }
+                if !rest.spans.is_empty() {
+                    words.push_front((space_style, rest));
+                }
+                line = start;
+                break;
+            } else if line.get_width() + 1 + word_width <= width {
+                line.push(" ", *space_style);
+                line.append(words.pop_front().unwrap().1);
+            } else {
+                break;
+            }
+        }
+        lines.push(line);
+    }
+    lines
 }
 
 /// Prints the string contents, ignoring '\n'.
 /// Wraps at ' ' or if unable in the middle of words.
 pub fn wrapping_print(
+    buffer: &mut Buffer,
     x: u16,
     y: u16,
     width: u16,
     height: u16,
-    fg_color: Option<Color>,
-    bg_color: Option<Color>,
+    style: Style,
     text: String,
 ) {
-    let text = text.replace('\n', "");
-    let mut text: Vec<&str> = text.split(' ').collect();
+    wrapping_print_lines(buffer, x, y, width, height, style, &to_lines(&text, false));
+}
+
+/// Prints styled lines, ignoring line breaks.
+/// Wraps at ' ' or if unable in the middle of words, span styles are applied on top of the style.
+pub fn wrapping_print_lines(
+    buffer: &mut Buffer,
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+    style: Style,
+    lines: &[Line],
+) {
+    // lines past the height are not printed, to show all of them put a box as high as the
+    // wrapped lines into a scroll view
+    let lines = wrap_lines(lines, width);
     for i in 0..height {
-        // todo this shit is pain
-        //  what tf do we do if the text length is to long?
-        //   just dont render the rest of the text and make it a scrollable box
-        //   indicate it and ignore the fact the user cant see the rest? --> currently implemented action
-        //   make the element reactive and still ignore the fact the user cant see it all do hower show the entire text scrollable at the bottom of the screen/when the element is selected
-        let mut line = String::new();
-        while line.len() < width as usize {
-            if text.len() == 0 {
-                break;
-            }
-            if line.len() == 0 {
-                if line.len() + text[0].len() < width as usize {
-                    line += text[0];
-                    text.remove(0);
-                } else {
-                    if text[0].chars().nth(width as usize - 1).unwrap() == '-' {
-                        line += &text[0].get(0..width as usize).unwrap();
-                        text[0] = &text[0][width as usize..text[0].len() - 1];
-                    } else {
-                        line = line + &text[0].get(0..width as usize - 1).unwrap() + "-"
-                    }
-                    break;
-                }
-            } else {
-                if line.len() + 1 + text[0].len() < width as usize {
-                    line = line + " " + text[0];
-                    text.remove(0);
-                } else {
-                    break;
-                }
-            }
-        }
-        print!(
-            "\x1b[{};{}H{}{}{}\x1b[0m",
+        let used = match lines.get(i as usize) {
+            Some(line) => print_line(buffer, x, y + i, style, line),
+            None => 0,
+        };
+        buffer.set_string(
+            x.saturating_add(used),
             y + i,
-            x,
-            force_colors(fg_color, bg_color),
-            line,
-            " ".repeat((width as usize).saturating_sub(line.len()))
+            &" ".repeat(width.saturating_sub(used) as usize),
+            style,
         );
     }
 }
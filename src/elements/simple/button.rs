@@ -1,6 +1,11 @@
 use crate::{
-    elements::wrapping_print,
+    buffer::Buffer,
+    elements::wrapping_print_lines,
     input::{Action, Event, KeyEvent, MouseEvent},
+    layout::Rect,
+    rich_text::to_lines,
+    style::{Attributes, Style},
+    theme::{themed, Role},
     tui::{Element, Reactive},
     Color,
 };
@@ -30,6 +35,12 @@ pub struct Button {
     pub selected_text_color: Option<Color>,
     /// Background color.
     pub selected_bg_color: Option<Color>,
+    /// Text attributes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attributes: Attributes,
+    /// Text attributes while selected.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub selected_attributes: Attributes,
     /// Action called upon interaction.
     #[cfg_attr(feature = "serde", serde(skip_deserializing, skip_serializing))]
     pub action: Action,
@@ -41,6 +52,9 @@ pub struct Button {
     pub visible: bool,
     /// Text content.
     pub text: String,
+    /// Parse the text as markup, see [rich_text](crate::rich_text).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub markup: bool,
 }
 
 impl Reactive for Button {
@@ -82,28 +96,35 @@ impl Reactive for Button {
 }
 
 impl Element for Button {
-    fn print(&self) {
-        if self.selected {
-            wrapping_print(
-                self.x,
-                self.y,
-                self.width,
-                self.height,
-                self.selected_text_color,
-                self.selected_bg_color,
-                self.text.clone(),
-            );
+    fn print(&self, buffer: &mut Buffer) {
+        let style = if self.selected {
+            Style::new(
+                themed(self.selected_text_color, Role::SelectedText),
+                themed(self.selected_bg_color, Role::Selected),
+            )
+            .with_attributes(self.selected_attributes)
+        } else if !self.enabled {
+            Style::new(
+                self.text_color.or(themed(None, Role::Disabled)),
+                themed(self.bg_color, Role::Surface),
+            )
+            .with_attributes(self.attributes)
         } else {
-            wrapping_print(
-                self.x,
-                self.y,
-                self.width,
-                self.height,
-                self.text_color,
-                self.bg_color,
-                self.text.clone(),
-            );
-        }
+            Style::new(
+                themed(self.text_color, Role::Text),
+                themed(self.bg_color, Role::Surface),
+            )
+            .with_attributes(self.attributes)
+        };
+        wrapping_print_lines(
+            buffer,
+            self.x,
+            self.y,
+            self.width,
+            self.height,
+            style,
+            &to_lines(&self.text, self.markup),
+        );
     }
 
     fn get_z(&self) -> u16 {
@@ -117,4 +138,15 @@ impl Element for Button {
     fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
     }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.x = rect.x;
+        self.y = rect.y;
+        self.width = rect.width;
+        self.height = rect.height;
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        Some(Rect::new(self.x, self.y, self.width, self.height))
+    }
 }
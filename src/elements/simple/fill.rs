@@ -0,0 +1,56 @@
+use crate::{buffer::Buffer, layout::Rect, tui::Element, Color};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Tui element that blends a color over an area, keeping the content underneath.
+///
+/// A translucent color dims or tints what is below, like a backdrop behind a dialog or a
+/// shadow. An opaque color hides it.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Fill {
+    /// X position.
+    pub x: u16,
+    /// Y position.
+    pub y: u16,
+    /// Z position/printing priority.
+    pub z: u16,
+    /// Area width.
+    pub width: u16,
+    /// Area height.
+    pub height: u16,
+    /// Fill color, usually translucent.
+    pub color: Color,
+    /// Element visibility.
+    pub visible: bool,
+}
+
+impl Element for Fill {
+    fn print(&self, buffer: &mut Buffer) {
+        buffer.tint(self.x, self.y, self.width, self.height, self.color);
+    }
+
+    fn get_z(&self) -> u16 {
+        self.z
+    }
+
+    fn get_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.x = rect.x;
+        self.y = rect.y;
+        self.width = rect.width;
+        self.height = rect.height;
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        Some(Rect::new(self.x, self.y, self.width, self.height))
+    }
+}
@@ -1,4 +1,11 @@
-use crate::{force_colors, tui::Element, Color};
+use crate::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Attributes, Style},
+    theme::{themed, Role},
+    tui::Element,
+    Color,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -21,6 +28,9 @@ pub struct Box {
     pub line_color: Option<Color>,
     /// Background color.
     pub bg_color: Option<Color>,
+    /// Line attributes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attributes: Attributes,
     /// Element visibility.
     pub visible: bool,
     /// Set of line drawing characters to use.
@@ -28,40 +38,39 @@ pub struct Box {
 }
 
 impl Element for Box {
-    fn print(&self) {
-        print!(
-            "\x1b[{};{}H{}{}{}{}",
-            self.y,
+    fn print(&self, buffer: &mut Buffer) {
+        let style = Style::new(
+            themed(self.line_color, Role::Border),
+            themed(self.bg_color, Role::Surface),
+        )
+        .with_attributes(self.attributes);
+        let inner_width = self.width.saturating_sub(2) as usize;
+        buffer.set_string(
             self.x,
-            force_colors(self.line_color, self.bg_color),
-            self.line_set.top_left,
-            self.line_set
-                .horizontal
-                .to_string()
-                .repeat(self.width.saturating_sub(2) as usize),
-            self.line_set.top_right
+            self.y,
+            &(self.line_set.top_left.to_string()
+                + &self.line_set.horizontal.to_string().repeat(inner_width)
+                + &self.line_set.top_right.to_string()),
+            style,
         );
-        for i in 1..self.height - 1 {
-            print!(
-                "\x1b[{};{}H{}{}{}",
-                self.y + i,
+        for i in 1..self.height.saturating_sub(1) {
+            buffer.set_string(
                 self.x,
-                self.line_set.vertical,
-                " ".repeat(self.width.saturating_sub(2) as usize),
-                self.line_set.vertical,
-            )
+                self.y + i,
+                &(self.line_set.vertical.to_string()
+                    + &" ".repeat(inner_width)
+                    + &self.line_set.vertical.to_string()),
+                style,
+            );
         }
-        print!(
-            "\x1b[{};{}H{}{}{}\x1b[0m",
-            self.y + self.height - 1,
+        buffer.set_string(
             self.x,
-            self.line_set.bottom_left,
-            self.line_set
-                .horizontal
-                .to_string()
-                .repeat(self.width.saturating_sub(2) as usize),
-            self.line_set.bottom_right
-        )
+            self.y + self.height.saturating_sub(1),
+            &(self.line_set.bottom_left.to_string()
+                + &self.line_set.horizontal.to_string().repeat(inner_width)
+                + &self.line_set.bottom_right.to_string()),
+            style,
+        );
     }
 
     fn get_z(&self) -> u16 {
@@ -75,6 +84,17 @@ impl Element for Box {
     fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
     }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.x = rect.x;
+        self.y = rect.y;
+        self.width = rect.width;
+        self.height = rect.height;
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        Some(Rect::new(self.x, self.y, self.width, self.height))
+    }
 }
 
 /// Defines a pallet of line drawing characters.
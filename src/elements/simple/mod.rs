@@ -1,13 +1,17 @@
 //! Contains basic elements.
 
 mod button;
+mod fill;
 mod interactable;
 pub mod line_box;
 mod text;
 mod text_box;
+mod text_input;
 
 pub use button::Button;
+pub use fill::Fill;
 pub use interactable::Interactable;
 pub use line_box::Box;
 pub use text::Text;
 pub use text_box::TextBox;
+pub use text_input::TextInput;
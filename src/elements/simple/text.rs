@@ -1,4 +1,13 @@
-use crate::{elements::print, tui::Element, Color};
+use crate::{
+    buffer::Buffer,
+    elements::print_lines,
+    layout::Rect,
+    rich_text::to_lines,
+    style::{Attributes, Style},
+    theme::{themed, Role},
+    tui::Element,
+    Color,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -17,20 +26,30 @@ pub struct Text {
     pub text_color: Option<Color>,
     /// Background color.
     pub bg_color: Option<Color>,
+    /// Text attributes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attributes: Attributes,
     /// Element visibility.
     pub visible: bool,
     /// Text content.
     pub text: String,
+    /// Parse the text as markup, see [rich_text](crate::rich_text).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub markup: bool,
 }
 
 impl Element for Text {
-    fn print(&self) {
-        print(
+    fn print(&self, buffer: &mut Buffer) {
+        print_lines(
+            buffer,
             self.x,
             self.y,
-            self.text_color,
-            self.bg_color,
-            self.text.clone(),
+            Style::new(
+                themed(self.text_color, Role::Text),
+                themed(self.bg_color, Role::Surface),
+            )
+            .with_attributes(self.attributes),
+            &to_lines(&self.text, self.markup),
         );
     }
 
@@ -45,4 +64,15 @@ impl Element for Text {
     fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
     }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.x = rect.x;
+        self.y = rect.y;
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        let lines = to_lines(&self.text, self.markup);
+        let width = lines.iter().map(|line| line.get_width()).max().unwrap_or(0);
+        Some(Rect::new(self.x, self.y, width as u16, lines.len() as u16))
+    }
 }
@@ -1,4 +1,13 @@
-use crate::{elements::wrapping_print, tui::Element, Color};
+use crate::{
+    buffer::Buffer,
+    elements::{wrap_lines, wrapping_print_lines},
+    layout::Rect,
+    rich_text::to_lines,
+    style::{Attributes, Style},
+    theme::{themed, Role},
+    tui::Element,
+    Color,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -21,22 +30,42 @@ pub struct TextBox {
     pub text_color: Option<Color>,
     /// Background color.
     pub bg_color: Option<Color>,
+    /// Text attributes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attributes: Attributes,
     /// Element visibility. <https://docs.unity3d.com/ScriptReference/Behaviour-enabled.html>
     pub visible: bool,
     /// Text content.
     pub text: String,
+    /// Parse the text as markup, see [rich_text](crate::rich_text).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub markup: bool,
+}
+
+impl TextBox {
+    /// Returns the number of lines the text wraps into at the current width.
+    ///
+    /// Lines past the height are not printed, a box of this height inside of a
+    /// [ScrollView](crate::elements::complex::ScrollView) shows all of them.
+    pub fn get_line_count(&self) -> u16 {
+        wrap_lines(&to_lines(&self.text, self.markup), self.width).len() as u16
+    }
 }
 
 impl Element for TextBox {
-    fn print(&self) {
-        wrapping_print(
+    fn print(&self, buffer: &mut Buffer) {
+        wrapping_print_lines(
+            buffer,
             self.x,
             self.y,
             self.width,
             self.height,
-            self.text_color,
-            self.bg_color,
-            self.text.clone(),
+            Style::new(
+                themed(self.text_color, Role::Text),
+                themed(self.bg_color, Role::Surface),
+            )
+            .with_attributes(self.attributes),
+            &to_lines(&self.text, self.markup),
         );
     }
 
@@ -51,4 +80,15 @@ impl Element for TextBox {
     fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
     }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.x = rect.x;
+        self.y = rect.y;
+        self.width = rect.width;
+        self.height = rect.height;
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        Some(Rect::new(self.x, self.y, self.width, self.height))
+    }
 }
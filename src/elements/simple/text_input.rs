@@ -0,0 +1,269 @@
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::{
+    buffer::Buffer,
+    cursor::Cursor,
+    input::callbacks::{
+        APPLICATION_SEQUENCE, ARROW_LEFT, ARROW_RIGHT, CONTROL_SEQUENCE, DELETE, ESCAPE,
+    },
+    input::observers::{get_mouse_generation, SEQUENCE_TIMEOUT},
+    input::{KeyEvent, MouseEvent},
+    layout::Rect,
+    style::Style,
+    theme::{themed, Role},
+    tui::{Element, Reactive},
+    unicode, Color,
+};
+
+/// Tui element for entering a single line of text.
+///
+/// Printable ascii keys are inserted at the cursor, backspace removes the character before it
+/// and the left and right arrow keys move it. The cursor is shown while the input is selected,
+/// text wider than the input scrolls so the cursor stays visible.
+pub struct TextInput {
+    /// X position.
+    pub x: u16,
+    /// Y position.
+    pub y: u16,
+    /// Z position/printing priority.
+    pub z: u16,
+    /// Input width.
+    pub width: u16,
+    /// Foreground color.
+    pub text_color: Option<Color>,
+    /// Background color.
+    pub bg_color: Option<Color>,
+    /// Foreground color while selected.
+    pub selected_text_color: Option<Color>,
+    /// Background color while selected.
+    pub selected_bg_color: Option<Color>,
+    /// Element selection.
+    pub selected: bool,
+    /// Element functionality.
+    pub enabled: bool,
+    /// Element visibility.
+    pub visible: bool,
+    /// Entered text and cursor position.
+    state: Mutex<InputState>,
+}
+
+/// Entered text and input in progress.
+#[derive(Default)]
+struct InputState {
+    /// Entered text.
+    text: String,
+    /// Byte index of the cursor in the text, always at a grapheme boundary.
+    cursor: usize,
+    /// Bytes of an unfinished escape sequence.
+    sequence: Vec<u8>,
+    /// Time the unfinished sequence started.
+    sequence_start: Option<Instant>,
+    /// Mouse generation when the unfinished sequence started, see [get_mouse_generation].
+    mouse_generation: usize,
+}
+
+impl InputState {
+    /// Returns the byte length of the grapheme before the cursor, 0 at the start.
+    fn get_previous_len(&self) -> usize {
+        unicode::graphemes(&self.text[..self.cursor])
+            .last()
+            .map_or(0, |grapheme| grapheme.len())
+    }
+
+    /// Returns the byte length of the grapheme after the cursor, 0 at the end.
+    fn get_next_len(&self) -> usize {
+        unicode::graphemes(&self.text[self.cursor..])
+            .next()
+            .map_or(0, |grapheme| grapheme.len())
+    }
+
+    /// Returns the byte index the text is shown from so the cursor fits into the width.
+    fn get_start(&self, width: u16) -> usize {
+        let mut start = 0;
+        // leaves room for the cursor after the last character
+        while start < self.cursor
+            && unicode::width(&self.text[start..self.cursor]) >= width as usize
+        {
+            start += unicode::graphemes(&self.text[start..])
+                .next()
+                .map_or(1, |grapheme| grapheme.len());
+        }
+        start
+    }
+}
+
+impl TextInput {
+    /// Creates an empty, visible and enabled input at the coordinates.
+    pub fn new(x: u16, y: u16, width: u16) -> TextInput {
+        TextInput {
+            x,
+            y,
+            z: 0,
+            width,
+            text_color: None,
+            bg_color: None,
+            selected_text_color: None,
+            selected_bg_color: None,
+            selected: false,
+            enabled: true,
+            visible: true,
+            state: Mutex::new(InputState::default()),
+        }
+    }
+
+    /// Returns the entered text.
+    pub fn get_text(&self) -> String {
+        self.state.lock().unwrap().text.clone()
+    }
+
+    /// Replaces the text, moving the cursor to its end.
+    pub fn set_text(&self, text: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.text = text.to_string();
+        state.cursor = text.len();
+    }
+}
+
+impl Reactive for TextInput {
+    fn keyboard(&self, data: KeyEvent) {
+        let mut state = self.state.lock().unwrap();
+        let stale = state.mouse_generation != get_mouse_generation()
+            || state
+                .sequence_start
+                .is_some_and(|start| start.elapsed() >= SEQUENCE_TIMEOUT);
+        if stale {
+            // the start of a mouse event or a lone escape key
+            state.sequence.clear();
+        }
+        if state.sequence.is_empty() {
+            state.sequence_start = Some(Instant::now());
+            state.mouse_generation = get_mouse_generation();
+        }
+        if data.code == ESCAPE || !state.sequence.is_empty() {
+            state.sequence.push(data.code);
+            let sequence = std::mem::take(&mut state.sequence);
+            match sequence[..] {
+                // incomplete sequence, wait for the next byte
+                [ESCAPE] | [ESCAPE, CONTROL_SEQUENCE | APPLICATION_SEQUENCE] => {
+                    state.sequence = sequence;
+                }
+                [ESCAPE, CONTROL_SEQUENCE | APPLICATION_SEQUENCE, ARROW_LEFT] => {
+                    state.cursor -= state.get_previous_len();
+                }
+                [ESCAPE, CONTROL_SEQUENCE | APPLICATION_SEQUENCE, ARROW_RIGHT] => {
+                    state.cursor += state.get_next_len();
+                }
+                // other sequences have no meaning for text entry
+                _ => {}
+            }
+            return;
+        }
+        match data.code {
+            DELETE => {
+                let end = state.cursor;
+                state.cursor -= state.get_previous_len();
+                let start = state.cursor;
+                state.text.replace_range(start..end, "");
+            }
+            b' '..=b'~' => {
+                let cursor = state.cursor;
+                state.text.insert(cursor, data.code as char);
+                state.cursor += 1;
+            }
+            _ => {}
+        }
+    }
+
+    /// Drops the unfinished sequence, the start of the mouse event also reaches the key events.
+    fn mouse(&self, _data: MouseEvent) {
+        self.state.lock().unwrap().sequence.clear();
+    }
+
+    fn get_x(&self) -> u16 {
+        self.x
+    }
+
+    fn get_y(&self) -> u16 {
+        self.y
+    }
+
+    fn get_width(&self) -> u16 {
+        self.width
+    }
+
+    fn get_height(&self) -> u16 {
+        1
+    }
+
+    fn set_selected(&mut self, selected: bool) {
+        self.selected = selected;
+    }
+
+    fn get_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns a bar cursor behind the character before it while selected.
+    fn get_cursor(&self) -> Option<Cursor> {
+        if !self.selected || self.width == 0 {
+            return None;
+        }
+        let state = self.state.lock().unwrap();
+        let start = state.get_start(self.width);
+        let x = self.x + unicode::width(&state.text[start..state.cursor]) as u16;
+        Some(Cursor::bar(x, self.y))
+    }
+}
+
+impl Element for TextInput {
+    fn print(&self, buffer: &mut Buffer) {
+        let style = if self.selected {
+            Style::new(
+                themed(self.selected_text_color, Role::SelectedText),
+                themed(self.selected_bg_color, Role::Selected),
+            )
+        } else if !self.enabled {
+            Style::new(
+                self.text_color.or(themed(None, Role::Disabled)),
+                themed(self.bg_color, Role::Surface),
+            )
+        } else {
+            Style::new(
+                themed(self.text_color, Role::Text),
+                themed(self.bg_color, Role::Surface),
+            )
+        };
+        let state = self.state.lock().unwrap();
+        let text = &state.text[state.get_start(self.width)..];
+        let text = unicode::truncate(text, self.width as usize);
+        let padding = " ".repeat(self.width as usize - unicode::width(text));
+        buffer.set_string(self.x, self.y, &(text.to_string() + &padding), style);
+    }
+
+    fn get_z(&self) -> u16 {
+        self.z
+    }
+
+    fn get_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.x = rect.x;
+        self.y = rect.y;
+        self.width = rect.width;
+    }
+
+    fn get_rect(&self) -> Option<Rect> {
+        Some(Rect::new(self.x, self.y, self.width, 1))
+    }
+}
@@ -1 +1,61 @@
 //! Error definitions.
+
+use std::error::Error;
+use std::fmt;
+
+/// Text could not be parsed as a color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorError {
+    /// The text that failed to parse.
+    pub text: String,
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid color: {:?}", self.text)
+    }
+}
+
+impl Error for ParseColorError {}
+
+/// A terminal session was started while another one is active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionActiveError;
+
+impl fmt::Display for SessionActiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a terminal session is already active")
+    }
+}
+
+impl Error for SessionActiveError {}
+
+/// A theme file could not be loaded.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum LoadThemeError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file content is not a valid theme.
+    Parse(serde_json::Error),
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for LoadThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadThemeError::Io(error) => write!(f, "unable to read theme: {}", error),
+            LoadThemeError::Parse(error) => write!(f, "invalid theme: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Error for LoadThemeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadThemeError::Io(error) => Some(error),
+            LoadThemeError::Parse(error) => Some(error),
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Exports rendered frames as plain text, ansi escaped text, html or svg.
+//!
+//! Use [TUI::screenshot](crate::tui::TUI::screenshot) to get the current frame of a tui. Colors
+//! the terminal would pick, unset and translucent ones, are taken from the current theme or
+//! the default [Theme] if none is set.
+
+use std::fmt::Write;
+
+use crate::buffer::Buffer;
+use crate::colors::Color;
+use crate::style::{Style, Underline};
+use crate::theme::{get_theme, Theme};
+
+/// Width of a cell in svg exports, in pixels.
+pub const SVG_CELL_WIDTH: u16 = 10;
+
+/// Height of a cell in svg exports, in pixels.
+pub const SVG_CELL_HEIGHT: u16 = 20;
+
+/// Output formats of an export.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ExportFormat {
+    /// Characters only.
+    Text,
+    /// Characters with ansi escape sequences, can be printed with `cat`.
+    Ansi,
+    /// Standalone html page with inline styles.
+    Html,
+    /// Svg image with a monospace grid.
+    Svg,
+}
+
+impl ExportFormat {
+    /// Returns the format matching the file extension, if any.
+    pub fn from_extension(extension: &str) -> Option<ExportFormat> {
+        match extension.to_ascii_lowercase().as_str() {
+            "txt" => Some(ExportFormat::Text),
+            "ans" | "ansi" => Some(ExportFormat::Ansi),
+            "html" | "htm" => Some(ExportFormat::Html),
+            "svg" => Some(ExportFormat::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the buffer in the format.
+pub fn export(buffer: &Buffer, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Text => to_text(buffer),
+        ExportFormat::Ansi => to_ansi(buffer),
+        ExportFormat::Html => to_html(buffer),
+        ExportFormat::Svg => to_svg(buffer),
+    }
+}
+
+/// Returns the characters of the buffer, one line per row.
+pub fn to_text(buffer: &Buffer) -> String {
+    buffer
+        .get_lines()
+        .iter()
+        .map(|line| line.trim_end().to_string() + "\n")
+        .collect()
+}
+
+/// Returns the buffer as ansi escaped text, one line per row.
+pub fn to_ansi(buffer: &Buffer) -> String {
+    let mut output = String::new();
+    for y in 1..=buffer.height {
+        for run in runs(buffer, y) {
+            output += &run.style.to_escape();
+            output += &run.text;
+        }
+        output += "\x1b[0m\n";
+    }
+    output
+}
+
+/// Returns the buffer as a standalone html page.
+pub fn to_html(buffer: &Buffer) -> String {
+    let theme = get_theme().unwrap_or_default();
+    let mut output = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n</head>\n\
+         <body style=\"margin: 0; background-color: {};\">\n\
+         <pre style=\"margin: 0; font-family: monospace; line-height: 1.2; color: {};\">",
+        theme.surface, theme.text
+    );
+    for y in 1..=buffer.height {
+        for run in runs(buffer, y) {
+            let (fg_color, bg_color) = resolve(run.style, &theme);
+            let _ = write!(
+                output,
+                "<span style=\"color: {}; background-color: {};{}\">{}</span>",
+                fg_color,
+                bg_color,
+                attributes_css(run.style),
+                escape_xml(&run.text)
+            );
+        }
+        output += "\n";
+    }
+    output + "</pre>\n</body>\n</html>\n"
+}
+
+/// Returns the buffer as an svg image, each cell is [SVG_CELL_WIDTH] by [SVG_CELL_HEIGHT] pixels.
+pub fn to_svg(buffer: &Buffer) -> String {
+    let theme = get_theme().unwrap_or_default();
+    let width = buffer.width as u32 * SVG_CELL_WIDTH as u32;
+    let height = buffer.height as u32 * SVG_CELL_HEIGHT as u32;
+    let mut backgrounds = String::new();
+    let mut texts = String::new();
+    for y in 1..=buffer.height {
+        let top = (y - 1) as u32 * SVG_CELL_HEIGHT as u32;
+        for run in runs(buffer, y) {
+            let (fg_color, bg_color) = resolve(run.style, &theme);
+            let left = (run.x - 1) as u32 * SVG_CELL_WIDTH as u32;
+            let run_width = run.width as u32 * SVG_CELL_WIDTH as u32;
+            if bg_color != theme.surface {
+                let _ = writeln!(
+                    backgrounds,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    left, top, run_width, SVG_CELL_HEIGHT, bg_color
+                );
+            }
+            if run.text.trim().is_empty() && run.style.attributes.underline == Underline::None {
+                continue;
+            }
+            let _ = writeln!(
+                texts,
+                "<text x=\"{}\" y=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\" \
+                 fill=\"{}\" style=\"{}\">{}</text>",
+                left,
+                top + SVG_CELL_HEIGHT as u32 * 3 / 4,
+                run_width,
+                fg_color,
+                attributes_css(run.style).trim_start(),
+                escape_xml(&run.text)
+            );
+        }
+    }
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"{}\" \
+         xml:space=\"preserve\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n{}{}</svg>\n",
+        SVG_CELL_HEIGHT * 4 / 5,
+        theme.surface,
+        backgrounds,
+        texts
+    )
+}
+
+/// Cells of a row next to each other sharing a style.
+struct Run {
+    /// X position of the first cell.
+    x: u16,
+    /// Number of cells covered.
+    width: u16,
+    /// Style of all cells.
+    style: Style,
+    /// Symbols of all cells.
+    text: String,
+}
+
+/// Returns the row split into runs of cells sharing a style.
+///
+/// Cells covered by a wide grapheme belong to the run of the grapheme.
+fn runs(buffer: &Buffer, y: u16) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    for x in 1..=buffer.width {
+        let cell = buffer.get(x, y).unwrap();
+        match runs.last_mut() {
+            Some(run) if cell.symbol.is_empty() || run.style == cell.style => {
+                run.width += 1;
+                run.text += &cell.symbol;
+            }
+            _ => runs.push(Run {
+                x,
+                width: 1,
+                style: cell.style,
+                text: if cell.symbol.is_empty() {
+                    " ".to_string()
+                } else {
+                    cell.symbol.clone()
+                },
+            }),
+        }
+    }
+    runs
+}
+
+/// Returns the opaque foreground and background colors the style is shown with.
+fn resolve(style: Style, theme: &Theme) -> (Color, Color) {
+    let bg_color = style
+        .bg_color
+        .map_or(theme.surface, |color| color.over(theme.surface));
+    let fg_color = style
+        .fg_color
+        .map_or(theme.text, |color| color.over(bg_color));
+    if style.attributes.reverse {
+        (bg_color, fg_color)
+    } else {
+        (fg_color, bg_color)
+    }
+}
+
+/// Returns the css declarations of the style attributes, each with a leading space.
+fn attributes_css(style: Style) -> String {
+    let attributes = style.attributes;
+    let mut css = String::new();
+    if attributes.bold {
+        css += " font-weight: bold;";
+    }
+    if attributes.italic {
+        css += " font-style: italic;";
+    }
+    if attributes.dim {
+        css += " opacity: 0.5;";
+    }
+    let mut lines = Vec::new();
+    if attributes.underline != Underline::None {
+        lines.push("underline");
+    }
+    if attributes.strikethrough {
+        lines.push("line-through");
+    }
+    if lines.is_empty() {
+        return css;
+    }
+    let kind = match attributes.underline {
+        Underline::Double => " double",
+        Underline::Curly => " wavy",
+        Underline::Dotted => " dotted",
+        Underline::Dashed => " dashed",
+        _ => "",
+    };
+    let color = attributes
+        .underline_color
+        .map(|color| format!(" {}", color))
+        .unwrap_or_default();
+    css + &format!(" text-decoration: {}{}{};", lines.join(" "), kind, color)
+}
+
+/// Escapes characters with a meaning in html and xml.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -10,6 +10,9 @@ pub const MOUSE_LEFT_DRAG: u8 = 64;
 pub const MOUSE_MIDDLE_DRAG: u8 = 65;
 pub const MOUSE_RIGHT_DRAG: u8 = 66;
 pub const MOUSE_MOVE: u8 = 67;
+// the wheel is reported as buttons 4 and 5, without release events
+pub const MOUSE_WHEEL_UP: u8 = 96;
+pub const MOUSE_WHEEL_DOWN: u8 = 97;
 // designator for the start of mouse events
 pub const MOUSE_EVENT_START: u8 = 77;
 // designator for the end of mouse events currently unused
@@ -26,6 +29,23 @@ pub const MOUSE_EVENT_END: u8 = 91;
 // wierd raw mode specific keycodes
 pub const RAW_ENTER: u8 = 13;
 
+// special keys are sent as escape sequences, one key event per byte:
+//     escape, '[' and the final byte for arrow keys, e.g. 27 91 65 for arrow up
+//     escape, '[', a number and '~' for page keys, e.g. 27 91 53 126 for page up
+// arrow keys start with escape and 'O' instead of '[' in application cursor mode
+pub const ESCAPE: u8 = 27;
+pub const CONTROL_SEQUENCE: u8 = 91;
+pub const APPLICATION_SEQUENCE: u8 = 79;
+pub const ARROW_UP: u8 = 65;
+pub const ARROW_DOWN: u8 = 66;
+pub const ARROW_RIGHT: u8 = 67;
+pub const ARROW_LEFT: u8 = 68;
+pub const PAGE_UP: u8 = 53;
+pub const PAGE_DOWN: u8 = 54;
+pub const SEQUENCE_END: u8 = 126;
+// sent by the backspace key
+pub const DELETE: u8 = 127;
+
 // direct input specific codes
 // in direct input
 //     ctrl + c closes the software
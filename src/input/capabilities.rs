@@ -0,0 +1,238 @@
+//! Terminal capability queries and their replies.
+//!
+//! The queries are answered through the input stream, see
+//! [Input::query_capabilities](crate::input::Input::query_capabilities). Primary device
+//! attributes are requested last, every terminal answers them and replies arrive in order.
+
+use crate::Color;
+
+/// Requests the foreground color (OSC 10), background color (OSC 11), terminal name and version
+/// (XTVERSION), synchronized output and SGR mouse support (DECRQM), kitty keyboard protocol
+/// flags, secondary and primary device attributes (DA2, DA1).
+pub const CAPABILITY_QUERIES: &str = concat!(
+    "\x1b]10;?\x1b\\",
+    "\x1b]11;?\x1b\\",
+    "\x1b[>0q",
+    "\x1b[?2026$p",
+    "\x1b[?1006$p",
+    "\x1b[?u",
+    "\x1b[>c",
+    "\x1b[c",
+);
+
+/// Terminals known to support true color that identify themselves through XTVERSION.
+const TRUECOLOR_TERMINALS: [&str; 7] = [
+    "kitty", "wezterm", "foot", "iterm2", "ghostty", "contour", "mintty",
+];
+
+/// Replies longer than this are not treated as replies.
+const MAX_REPLY_LENGTH: usize = 512;
+
+/// Features the terminal reported.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Capabilities {
+    /// Whether the terminal answered at all, everything is unknown otherwise.
+    pub responded: bool,
+    /// 24-bit colors, from the environment or a known terminal name.
+    pub truecolor: bool,
+    /// SGR extended mouse reporting, mode 1006.
+    pub sgr_mouse: bool,
+    /// Kitty keyboard protocol.
+    pub kitty_keyboard: bool,
+    /// Synchronized output, mode 2026.
+    pub synchronized_output: bool,
+    /// Sixel graphics, primary device attribute 4.
+    pub sixel: bool,
+    /// Clipboard access through OSC 52, primary device attribute 52.
+    pub clipboard: bool,
+    /// Default foreground color.
+    pub foreground: Option<Color>,
+    /// Default background color.
+    pub background: Option<Color>,
+    /// Terminal name and version as reported by XTVERSION.
+    pub name: Option<String>,
+    /// Primary device attributes, the first one is the conformance level.
+    pub device_attributes: Vec<u16>,
+    /// Secondary device attributes: terminal type, firmware version and rom cartridge.
+    pub secondary_attributes: Vec<u16>,
+}
+
+/// Separates query replies from the regular input stream.
+///
+/// Bytes that don't belong to a reply, like keys pressed while waiting, are kept in order.
+#[derive(Debug, Default)]
+pub struct ReplyParser {
+    /// Features found so far.
+    capabilities: Capabilities,
+    /// Escape sequence currently read.
+    sequence: Vec<u8>,
+    /// Input that is not part of a reply.
+    passthrough: Vec<u8>,
+    /// Whether the final reply arrived.
+    done: bool,
+}
+
+impl ReplyParser {
+    #[allow(missing_docs)]
+    pub fn new() -> ReplyParser {
+        ReplyParser::default()
+    }
+
+    /// Returns whether the primary device attributes, the last reply, arrived.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Processes the next input byte.
+    pub fn feed(&mut self, byte: u8) {
+        if self.sequence.is_empty() {
+            if byte == 0x1b && !self.done {
+                self.sequence.push(byte);
+            } else {
+                self.passthrough.push(byte);
+            }
+            return;
+        }
+        self.sequence.push(byte);
+        let sequence = &self.sequence;
+        let complete = match sequence[1] {
+            b'[' => sequence.len() > 2 && (0x40..=0x7e).contains(&byte),
+            b']' => byte == 0x07 || sequence.ends_with(b"\x1b\\"),
+            b'P' => sequence.ends_with(b"\x1b\\"),
+            // not a reply, like alt + key
+            _ => {
+                self.passthrough.append(&mut self.sequence);
+                return;
+            }
+        };
+        if complete {
+            let sequence = std::mem::take(&mut self.sequence);
+            if !self.interpret(&sequence) {
+                self.passthrough.extend(sequence);
+            }
+        } else if sequence.len() > MAX_REPLY_LENGTH {
+            self.passthrough.append(&mut self.sequence);
+        }
+    }
+
+    /// Returns whether a sequence that could be a reply is partly read.
+    pub fn is_pending(&self) -> bool {
+        !self.sequence.is_empty()
+    }
+
+    /// Treats the partly read sequence as regular input, used once no more input arrives.
+    pub fn flush(&mut self) {
+        self.passthrough.append(&mut self.sequence);
+    }
+
+    /// Returns the input read so far that is not part of a reply, without a partly read one.
+    pub fn take_passthrough(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.passthrough)
+    }
+
+    /// Returns the features found so far.
+    pub fn get_capabilities(&self) -> Capabilities {
+        let mut capabilities = self.capabilities.clone();
+        if let Some(name) = &capabilities.name {
+            let name = name.to_ascii_lowercase();
+            capabilities.truecolor |= TRUECOLOR_TERMINALS
+                .iter()
+                .any(|terminal| name.starts_with(terminal));
+        }
+        capabilities
+    }
+
+    /// Returns the features found and all input that is not part of a reply.
+    pub fn finish(mut self) -> (Capabilities, Vec<u8>) {
+        self.flush();
+        (self.get_capabilities(), self.passthrough)
+    }
+
+    /// Stores the content of a complete reply, returns false if it is none.
+    fn interpret(&mut self, sequence: &[u8]) -> bool {
+        let Ok(text) = std::str::from_utf8(&sequence[2..]) else {
+            return false;
+        };
+        let capabilities = &mut self.capabilities;
+        match sequence[1] {
+            b'[' => {
+                if let Some(attributes) = text.strip_prefix('?').and_then(|t| t.strip_suffix('c')) {
+                    capabilities.device_attributes = parse_numbers(attributes);
+                    capabilities.sixel = capabilities.device_attributes[1..].contains(&4);
+                    capabilities.clipboard = capabilities.device_attributes[1..].contains(&52);
+                    capabilities.responded = true;
+                    self.done = true;
+                } else if let Some(attributes) =
+                    text.strip_prefix('>').and_then(|t| t.strip_suffix('c'))
+                {
+                    capabilities.secondary_attributes = parse_numbers(attributes);
+                } else if let Some(report) =
+                    text.strip_prefix('?').and_then(|t| t.strip_suffix("$y"))
+                {
+                    let numbers = parse_numbers(report);
+                    let [mode, value] = numbers[..] else {
+                        return false;
+                    };
+                    // 1 set, 2 reset, 3 permanently set, 0 and 4 unsupported
+                    let supported = matches!(value, 1..=3);
+                    match mode {
+                        2026 => capabilities.synchronized_output = supported,
+                        1006 => capabilities.sgr_mouse = supported,
+                        _ => return false,
+                    }
+                } else if text.starts_with('?') && text.ends_with('u') {
+                    capabilities.kitty_keyboard = true;
+                } else {
+                    return false;
+                }
+            }
+            b']' => {
+                let text = text.trim_end_matches('\x07').trim_end_matches("\x1b\\");
+                let Some((number, color)) = text.split_once(';') else {
+                    return false;
+                };
+                let color = parse_x11_color(color);
+                match number {
+                    "10" => capabilities.foreground = color,
+                    "11" => capabilities.background = color,
+                    _ => return false,
+                }
+            }
+            b'P' => {
+                let Some(name) = text.strip_prefix(">|") else {
+                    return false;
+                };
+                capabilities.name = Some(name.trim_end_matches("\x1b\\").to_string());
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Parses ';' separated numbers, invalid ones become 0.
+fn parse_numbers(text: &str) -> Vec<u16> {
+    text.split(';')
+        .map(|number| number.parse().unwrap_or_default())
+        .collect()
+}
+
+/// Parses a `rgb:rrrr/gggg/bbbb` color with 1 to 4 hex digits per channel.
+fn parse_x11_color(text: &str) -> Option<Color> {
+    let channels = text
+        .strip_prefix("rgb:")?
+        .split('/')
+        .map(|channel| {
+            if !(1..=4).contains(&channel.len()) {
+                return None;
+            }
+            let value = u32::from_str_radix(channel, 16).ok()?;
+            let max = (1u32 << (4 * channel.len())) - 1;
+            Some((value * 255 / max) as u8)
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    let [r, g, b] = channels[..] else {
+        return None;
+    };
+    Some(Color::new(r, g, b))
+}
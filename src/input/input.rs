@@ -2,19 +2,23 @@
 
 use libc::{ioctl, pid_t, TIOCGWINSZ};
 use nix::pty::Winsize;
-use nix::sys::termios;
-use nix::sys::termios::Termios;
 use nix::unistd::Pid;
-use signal_hook::consts::signal::{SIGHUP, SIGINT, SIGQUIT, SIGTERM, SIGWINCH};
+use signal_hook::consts::signal::{SIGCONT, SIGHUP, SIGINT, SIGQUIT, SIGTERM, SIGTSTP, SIGWINCH};
 use signal_hook::iterator::Signals;
+use std::collections::VecDeque;
 use std::io::{Read, Write};
 use std::process::id;
 use std::sync::mpsc::Receiver;
 use std::sync::{mpsc, Arc, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{io, thread};
 
+use crate::colors::{detect_color_depth, ColorDepth};
+use crate::input::capabilities::{Capabilities, ReplyParser, CAPABILITY_QUERIES};
 use crate::input::observers::{DebugObserver, ExitObserver, ReloadObserver};
+use crate::recorder::Recorder;
+use crate::terminal::{restore, resume, suspend, Session, SessionOptions, Viewport};
+use crate::tui::request_redraw;
 
 use super::callbacks::MOUSE_EVENT_START;
 
@@ -91,6 +95,21 @@ pub struct MouseEvent {
     pub y: u8,
 }
 
+/// Describes a change of the terminal size.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ResizeEvent {
+    /// New width in columns.
+    pub width: u16,
+    /// New height in rows.
+    pub height: u16,
+}
+
+/// Structs that implement this trait can be used to attach functions to resize events.
+pub trait ResizeEventObserver: Sync + Send {
+    /// Called each time the terminal was resized, after the screen was cleared.
+    fn handle_resize_event(&self, data: ResizeEvent);
+}
+
 /// Structs that implement this trait can be used to attach functions to mouse events.
 pub trait MouseEventObserver: Sync + Send {
     /// Called each time a mouse event is received, after binding to input.
@@ -103,12 +122,18 @@ pub struct Input {
     ///
     /// See [TuiDebug].
     debug: Option<TuiDebug>,
-    /// Contains the terminal emulator state before enabling raw input mode.
-    return_state: Termios,
+    /// Terminal session, restores the terminal when dropped.
+    session: Session,
     /// Multithreaded receiver for emulator input events.
     input_rx: Receiver<[u8; 1]>,
+    /// Input read while waiting for query replies, handled before new input.
+    pending: VecDeque<u8>,
+    /// Filters replies to capability queries out of the input until the last one arrived.
+    late_replies: Option<ReplyParser>,
     /// Used to listen to system signals such as SIGQUIT.
     sys_signals: Signals,
+    /// Records all handled input.
+    recorder: Option<Recorder>,
     /// All observers to notify of key events.
     ///
     /// See [KeyEventObserver].
@@ -117,44 +142,14 @@ pub struct Input {
     ///
     /// See [MouseEventObserver].
     pub mouse_observers: Arc<RwLock<Vec<Arc<dyn MouseEventObserver>>>>,
+    /// All observers to notify of resize events.
+    ///
+    /// See [ResizeEventObserver].
+    pub resize_observers: Arc<RwLock<Vec<Arc<dyn ResizeEventObserver>>>>,
 }
 
-/// Enables the emulator raw mode, returns the previous state.
-///
-/// Completly disables input processing.
-///
-/// Some codes are different to direct input.
-fn set_raw_mode() -> Termios {
-    let mut tio = termios::tcgetattr(0).expect("Unable to get terminal attribute!");
-    let old = tio.clone();
-    termios::cfmakeraw(&mut tio);
-    termios::tcsetattr(0, termios::SetArg::TCSANOW, &tio).unwrap();
-    old
-}
-
-/// Enables the emulator direct input mode, returns the previous state.
-///
-/// Preffered to raw mode as it doesnt disable input processing.
-///
-/// Some codes are different to raw mode.
-fn set_direct_input() -> Termios {
-    let mut tio = termios::tcgetattr(0).expect("Unable to get terminal attribute!");
-    let old = tio.clone();
-    tio.input_flags.insert(termios::InputFlags::BRKINT);
-    tio.input_flags.remove(termios::InputFlags::IGNBRK);
-    tio.local_flags.remove(termios::LocalFlags::ECHO);
-    tio.local_flags.remove(termios::LocalFlags::ICANON);
-    termios::tcsetattr(0, termios::SetArg::TCSANOW, &tio).unwrap();
-    old
-}
-
-/// Set mode of terminal emulator.
-fn set_mode(attr: Termios) {
-    termios::tcsetattr(0, termios::SetArg::TCSANOW, &attr).unwrap();
-}
-
-/// Returns the current emulator size.
-fn get_size() -> (u16, u16) {
+/// Returns the current emulator size as rows and columns.
+pub(crate) fn get_size() -> (u16, u16) {
     let mut winsize = Winsize {
         ws_row: 0,
         ws_col: 0,
@@ -184,6 +179,21 @@ pub fn debug_pos(x: u8, y: u8) {
 impl Input {
     #[allow(missing_docs)] // UwU
     pub fn new(debug: bool, raw: bool) -> Input {
+        Input::with_options(
+            debug,
+            SessionOptions {
+                raw,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Creates the input handler and starts a terminal session with the given options.
+    ///
+    /// Panics if another session is active, see [Session::new].
+    pub fn with_options(debug: bool, options: SessionOptions) -> Input {
+        let session = Session::new(options).unwrap();
+        let raw = options.raw;
         let (input_tx, input_rx) = mpsc::channel();
         thread::spawn(move || loop {
             let mut buffer = [0u8; 1];
@@ -208,15 +218,18 @@ impl Input {
             } else {
                 None
             },
-            return_state: if raw {
-                set_raw_mode()
-            } else {
-                set_direct_input()
-            },
+            session,
             input_rx,
-            sys_signals: Signals::new(&[SIGWINCH, SIGTERM, SIGINT, SIGQUIT, SIGHUP]).unwrap(),
+            pending: VecDeque::new(),
+            late_replies: None,
+            sys_signals: Signals::new([
+                SIGWINCH, SIGTERM, SIGINT, SIGQUIT, SIGHUP, SIGTSTP, SIGCONT,
+            ])
+            .unwrap(),
+            recorder: None,
             key_observers: Arc::new(RwLock::new(Vec::new())),
             mouse_observers: Arc::new(RwLock::new(Vec::new())),
+            resize_observers: Arc::new(RwLock::new(Vec::new())),
         };
         if raw {
             input
@@ -238,25 +251,37 @@ impl Input {
                 .push(Arc::new(DebugObserver {}));
         }
 
-        print!("\x1b[0m\x1b[H\x1b[J\x1b[?25l\x1b[?1003h");
-
         input
     }
 
+    /// Returns the terminal session.
+    pub fn get_session(&self) -> &Session {
+        &self.session
+    }
+
     /// Handles system signals.
     pub fn handle_signals(&mut self) {
         for signal in self.sys_signals.pending() {
             match signal {
                 SIGWINCH => {
+                    let (height, width) = get_size();
                     if let Some(debug) = self.debug.as_mut() {
-                        (debug.height, debug.width) = get_size();
+                        (debug.height, debug.width) = (height, width);
+                    }
+                    if self.session.get_options().viewport == Viewport::Fullscreen {
+                        print!("\x1b[0m\x1b[H\x1b[J");
+                    }
+                    request_redraw();
+                    let event = ResizeEvent { width, height };
+                    for observer in self.resize_observers.read().unwrap().iter() {
+                        observer.handle_resize_event(event);
                     }
-                    print!("\x1b[0m\x1b[H\x1b[J");
                 }
+                SIGTSTP => suspend(),
+                SIGCONT => resume(),
                 SIGTERM | SIGINT | SIGQUIT | SIGHUP => {
-                    set_mode(self.return_state.clone());
-                    print!("\x1b[0m\x1b[H\x1b[J\x1b[?25h\x1b[?1003l");
-                    let _ = io::stdout().lock().flush();
+                    // exit skips destructors
+                    restore();
                     std::process::exit(0);
                 }
                 _ => unreachable!(),
@@ -264,30 +289,79 @@ impl Input {
         }
     }
 
+    /// Asks the terminal what it supports and waits up to the timeout for the replies.
+    ///
+    /// Replies are not passed on as input events, everything else read while waiting is handled
+    /// by the next [Input::handle_input_events] call. Replies arriving after the timeout are
+    /// dropped from the input as well, until the last one arrived.
+    pub fn query_capabilities(&mut self, timeout: Duration) -> Capabilities {
+        print!("{}", CAPABILITY_QUERIES);
+        let _ = io::stdout().lock().flush();
+        let deadline = Instant::now() + timeout;
+        let mut parser = self.late_replies.take().unwrap_or_default();
+        while !parser.is_done() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.input_rx.recv_timeout(remaining) {
+                Ok(input) => parser.feed(input[0]),
+                Err(_) => break,
+            }
+        }
+        let mut capabilities = parser.get_capabilities();
+        capabilities.truecolor |= detect_color_depth() == ColorDepth::TrueColor;
+        self.pending.extend(parser.take_passthrough());
+        if !parser.is_done() {
+            self.late_replies = Some(parser);
+        }
+        capabilities
+    }
+
+    /// Sets the recorder handled input is recorded with, stops recording if none.
+    ///
+    /// Usually the same recorder as the one of the backend.
+    pub fn set_recorder(&mut self, recorder: Option<Recorder>) {
+        self.recorder = recorder;
+    }
+
+    /// Returns the next input byte, waiting up to the timeout.
+    ///
+    /// Late replies to capability queries are skipped.
+    fn next_input(&mut self, timeout: Duration) -> Option<u8> {
+        let input = loop {
+            if let Some(input) = self.pending.pop_front() {
+                break input;
+            }
+            let input = self.input_rx.recv_timeout(timeout).ok();
+            let Some(parser) = self.late_replies.as_mut() else {
+                break input?[0];
+            };
+            match input {
+                Some(input) => parser.feed(input[0]),
+                // nothing follows, like after the escape key
+                None if parser.is_pending() => parser.flush(),
+                None => return None,
+            }
+            self.pending.extend(parser.take_passthrough());
+            if parser.is_done() {
+                self.late_replies = None;
+            }
+        };
+        if let Some(recorder) = &self.recorder {
+            let _ = recorder.record_input(&[input]);
+        }
+        Some(input)
+    }
+
     /// Handles input events.
     pub fn handle_input_events(&mut self, input_timeout: Duration) {
-        while let Some(input) = self.input_rx.recv_timeout(input_timeout).ok() {
+        while let Some(input) = self.next_input(input_timeout) {
             let mut mouse = [0u8; 3];
-            if input[0] == MOUSE_EVENT_START {
-                mouse[0] = self
-                    .input_rx
-                    .recv_timeout(Duration::from_millis(1))
-                    .unwrap_or([0u8])[0]
-                    .clone();
-                mouse[1] = self
-                    .input_rx
-                    .recv_timeout(Duration::from_millis(1))
-                    .unwrap_or([0u8])[0]
-                    .clone()
-                    .saturating_sub(32u8);
-                mouse[2] = self
-                    .input_rx
-                    .recv_timeout(Duration::from_millis(1))
-                    .unwrap_or([0u8])[0]
-                    .clone()
-                    .saturating_sub(32u8);
-                if self.debug.is_some() {
-                    self.debug.as_mut().unwrap().last_mouse_readout = mouse.clone();
+            if input == MOUSE_EVENT_START {
+                let timeout = Duration::from_millis(1);
+                mouse[0] = self.next_input(timeout).unwrap_or(0);
+                mouse[1] = self.next_input(timeout).unwrap_or(0).saturating_sub(32u8);
+                mouse[2] = self.next_input(timeout).unwrap_or(0).saturating_sub(32u8);
+                if let Some(debug) = self.debug.as_mut() {
+                    debug.last_mouse_readout = mouse;
                 }
             }
             if mouse[0] != 0 {
@@ -296,18 +370,16 @@ impl Input {
                     x: mouse[1],
                     y: mouse[2],
                 };
-                if self.debug.is_some() {
-                    self.debug.as_mut().unwrap().last_mouse_event = event.clone();
+                if let Some(debug) = self.debug.as_mut() {
+                    debug.last_mouse_event = event;
                 }
                 for observer in self.mouse_observers.read().unwrap().iter() {
                     observer.handle_mouse_event(event);
                 }
             } else {
-                let event = KeyEvent {
-                    code: input[0].clone(),
-                };
-                if self.debug.is_some() {
-                    self.debug.as_mut().unwrap().last_key_event = event.clone();
+                let event = KeyEvent { code: input };
+                if let Some(debug) = self.debug.as_mut() {
+                    debug.last_key_event = event;
                 }
                 for observer in self.key_observers.read().unwrap().iter() {
                     observer.handle_key_event(event);
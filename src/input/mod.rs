@@ -1,6 +1,8 @@
 //! Handles all ansi detectable input.
 
 pub mod callbacks;
+pub mod capabilities;
+#[allow(clippy::module_inception)]
 mod input;
 pub mod observers;
 
@@ -1,9 +1,14 @@
 //! Predefined event observers.
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 use crate::{
-    input::{debug_pos, exit, reload, KeyEvent, KeyEventObserver, MouseEvent, MouseEventObserver},
-    tui::{ReactiveTUI, RwLockReactive},
+    input::{
+        debug_pos, exit, reload, KeyEvent, KeyEventObserver, MouseEvent, MouseEventObserver,
+        ResizeEvent, ResizeEventObserver,
+    },
+    tui::{ReactiveTUI, RwLockReactive, TUI},
 };
 
 use super::callbacks::{KILL, MOUSE_MOVE, RELOAD};
@@ -56,9 +61,9 @@ impl KeyEventObserver for TuiKeyObserver {
             .reactive_elements
             .iter()
             .filter(|e| e.read().unwrap().get_enabled())
-            .map(|e| e.clone())
+            .cloned()
             .collect();
-        if reactive_elements.len() > 0 {
+        if !reactive_elements.is_empty() {
             for element in tui_write.reactive_elements.clone() {
                 element.write().unwrap().set_selected(false);
             }
@@ -69,11 +74,8 @@ impl KeyEventObserver for TuiKeyObserver {
                 tui_write.selected_element += tui_write.reactive_elements.len() - 1;
                 tui_write.selected_element %= tui_write.reactive_elements.len();
             }
-            let mut selected_element_write = reactive_elements
-                .get(tui_write.selected_element % reactive_elements.len())
-                .unwrap()
-                .write()
-                .unwrap();
+            let selected_element = tui_write.get_selected().unwrap();
+            let mut selected_element_write = selected_element.write().unwrap();
             selected_element_write.keyboard(data);
             selected_element_write.set_selected(true);
         } else {
@@ -82,6 +84,21 @@ impl KeyEventObserver for TuiKeyObserver {
     }
 }
 
+/// Incremented each time the tui handles a mouse event, see [get_mouse_generation].
+static MOUSE_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns a number that changes each time a [TuiMouseObserver] handles a mouse event.
+///
+/// The start of a mouse event also reaches the key observers, elements waiting for the rest of
+/// an escape sequence use it to notice that the bytes belonged to a mouse event.
+pub fn get_mouse_generation() -> usize {
+    MOUSE_GENERATION.load(Ordering::SeqCst)
+}
+
+/// Time after which an unfinished escape sequence is handled as separate keys, like a lone
+/// escape key.
+pub const SEQUENCE_TIMEOUT: Duration = Duration::from_millis(50);
+
 /// Mouse observer for element event handling.
 pub struct TuiMouseObserver {
     /// Reference to the tui.
@@ -90,6 +107,7 @@ pub struct TuiMouseObserver {
 
 impl MouseEventObserver for TuiMouseObserver {
     fn handle_mouse_event(&self, data: MouseEvent) {
+        MOUSE_GENERATION.fetch_add(1, Ordering::SeqCst);
         let tui_read = self.tui.read().unwrap();
         for element in &tui_read.reactive_elements {
             let element_lock = element.read().unwrap();
@@ -109,3 +127,15 @@ impl MouseEventObserver for TuiMouseObserver {
         }
     }
 }
+
+/// Resize observer redrawing a tui right away, see [TUI::handle_resize].
+pub struct TuiResizeObserver<T: TUI + Send + Sync> {
+    /// Reference to the tui.
+    pub tui: Arc<RwLock<T>>,
+}
+
+impl<T: TUI + Send + Sync> ResizeEventObserver for TuiResizeObserver<T> {
+    fn handle_resize_event(&self, data: ResizeEvent) {
+        self.tui.read().unwrap().handle_resize(data);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::layout::Rect;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Position along one axis, relative to a reference area.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Offset {
+    /// Cells after the start of the reference.
+    Start(u16),
+    /// Cells between the end of the element and the end of the reference.
+    End(u16),
+    /// Centered in the reference, moved by the cells.
+    Center(i16),
+    /// Percentage of the reference size after its start.
+    Percent(u16),
+    /// Outside of the reference, cells between the end of the element and its start.
+    Before(u16),
+    /// Outside of the reference, cells after its end.
+    After(u16),
+}
+
+/// Size along one axis, relative to a reference area.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Extent {
+    /// Size the element already has.
+    Keep,
+    /// Exact number of cells.
+    Length(u16),
+    /// Percentage of the reference size.
+    Percent(u16),
+    /// Size of the reference, less a [Offset::Start] or [Offset::End] offset.
+    Fill,
+}
+
+/// Describes where an element goes relative to the screen or another element.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Anchor {
+    /// Horizontal position.
+    pub x: Offset,
+    /// Vertical position.
+    pub y: Offset,
+    /// Width.
+    pub width: Extent,
+    /// Height.
+    pub height: Extent,
+}
+
+impl Anchor {
+    /// Full width bar of the given height at the bottom of the reference.
+    pub fn bottom_bar(height: u16) -> Anchor {
+        Anchor {
+            x: Offset::Start(0),
+            y: Offset::End(0),
+            width: Extent::Fill,
+            height: Extent::Length(height),
+        }
+    }
+
+    /// Full width bar of the given height at the top of the reference.
+    pub fn top_bar(height: u16) -> Anchor {
+        Anchor {
+            y: Offset::Start(0),
+            ..Anchor::bottom_bar(height)
+        }
+    }
+
+    /// Centered with a size in percent of the reference.
+    pub fn centered(width_percent: u16, height_percent: u16) -> Anchor {
+        Anchor {
+            x: Offset::Center(0),
+            y: Offset::Center(0),
+            width: Extent::Percent(width_percent),
+            height: Extent::Percent(height_percent),
+        }
+    }
+
+    /// Right of the reference with a gap, aligned to its top, keeping its size.
+    pub fn right_of(gap: u16) -> Anchor {
+        Anchor {
+            x: Offset::After(gap),
+            y: Offset::Start(0),
+            width: Extent::Keep,
+            height: Extent::Keep,
+        }
+    }
+
+    /// Below the reference with a gap, aligned to its left, keeping its size.
+    pub fn below(gap: u16) -> Anchor {
+        Anchor {
+            x: Offset::Start(0),
+            y: Offset::After(gap),
+            width: Extent::Keep,
+            height: Extent::Keep,
+        }
+    }
+
+    /// Returns the visible area of an element currently covering the given one.
+    ///
+    /// Parts before the screen, which starts at 1, are cut off, so the element starts at 1 and
+    /// shrinks by the cut amount. See [Anchor::place] for the whole area.
+    pub fn resolve(&self, reference: Rect, current: Rect) -> Rect {
+        let (x, y, width, height) = self.place(reference, current);
+        let cut = |start: i32, size: u16| {
+            // cut the part before the first cell
+            let cut = (1 - start).max(0);
+            (
+                (start + cut).min(u16::MAX as i32) as u16,
+                (size as i32 - cut).max(0) as u16,
+            )
+        };
+        let (x, width) = cut(x, width);
+        let (y, height) = cut(y, height);
+        Rect::new(x, y, width, height)
+    }
+
+    /// Returns the start x, start y, width and height of an element currently covering the
+    /// given one, the start can be before the screen.
+    pub fn place(&self, reference: Rect, current: Rect) -> (i32, i32, u16, u16) {
+        let (x, width) = resolve_axis(
+            self.x,
+            self.width,
+            reference.x,
+            reference.width,
+            current.width,
+        );
+        let (y, height) = resolve_axis(
+            self.y,
+            self.height,
+            reference.y,
+            reference.height,
+            current.height,
+        );
+        (x, y, width, height)
+    }
+}
+
+/// Returns the start and size along one axis.
+fn resolve_axis(
+    offset: Offset,
+    extent: Extent,
+    reference_start: u16,
+    reference_size: u16,
+    current_size: u16,
+) -> (i32, u16) {
+    let reference_start = reference_start as i32;
+    let reference_size = reference_size as i32;
+    let size = match extent {
+        Extent::Keep => current_size as i32,
+        Extent::Length(length) => length as i32,
+        Extent::Percent(percent) => reference_size * percent as i32 / 100,
+        Extent::Fill => match offset {
+            Offset::Start(cells) | Offset::End(cells) => reference_size - cells as i32,
+            _ => reference_size,
+        },
+    }
+    .clamp(0, u16::MAX as i32);
+    let reference_end = reference_start + reference_size;
+    let start = match offset {
+        Offset::Start(cells) => reference_start + cells as i32,
+        Offset::End(cells) => reference_end - cells as i32 - size,
+        Offset::Center(cells) => reference_start + (reference_size - size) / 2 + cells as i32,
+        Offset::Percent(percent) => reference_start + reference_size * percent as i32 / 100,
+        Offset::Before(cells) => reference_start - cells as i32 - size,
+        Offset::After(cells) => reference_end + cells as i32,
+    };
+    (start, size as u16)
+}
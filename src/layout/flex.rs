@@ -0,0 +1,214 @@
+use crate::layout::{grow, Padding, Rect};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Size of a child along the direction of a layout.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Constraint {
+    /// Exact number of cells.
+    Length(u16),
+    /// Percentage of the available space.
+    Percentage(u16),
+    /// Fraction of the available space.
+    Ratio(u32, u32),
+    /// At least this many cells, grows if nothing fills the space.
+    Min(u16),
+    /// At most this many cells, shrinks before other sizes.
+    Max(u16),
+    /// Share of the space left over, relative to the other fills.
+    Fill(u16),
+}
+
+/// Axis children are placed along.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Direction {
+    /// Left to right.
+    Horizontal,
+    /// Top to bottom.
+    #[default]
+    Vertical,
+}
+
+/// Placement of the children when they don't fill the space.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Alignment {
+    /// Packed at the start.
+    #[default]
+    Start,
+    /// Packed in the middle.
+    Center,
+    /// Packed at the end.
+    End,
+    /// First and last child at the edges, the free space between the others.
+    SpaceBetween,
+    /// Equal free space around and between all children.
+    SpaceEvenly,
+}
+
+/// Places children in a row or column.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Flex {
+    /// Axis the children are placed along.
+    pub direction: Direction,
+    /// Cells between children.
+    pub gap: u16,
+    /// Space left free inside the area.
+    pub padding: Padding,
+    /// Placement of the children when they don't fill the space.
+    pub alignment: Alignment,
+}
+
+impl Flex {
+    /// Creates a layout placing children left to right.
+    pub fn row() -> Flex {
+        Flex {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a layout placing children top to bottom.
+    pub fn column() -> Flex {
+        Flex {
+            direction: Direction::Vertical,
+            ..Default::default()
+        }
+    }
+
+    /// Splits the area into one rectangle per constraint, each spanning the whole cross axis.
+    ///
+    /// If the constraints don't fit, [Constraint::Max], [Constraint::Percentage] and
+    /// [Constraint::Ratio] sizes shrink first, then [Constraint::Length] and at last
+    /// [Constraint::Min], each starting with the last child.
+    pub fn split(&self, area: Rect, constraints: &[Constraint]) -> Vec<Rect> {
+        if constraints.is_empty() {
+            return Vec::new();
+        }
+        let inner = area.inner(self.padding);
+        let size = match self.direction {
+            Direction::Horizontal => inner.width,
+            Direction::Vertical => inner.height,
+        } as u32;
+        let gaps = self.gap as u32 * (constraints.len() as u32 - 1);
+        let available = size.saturating_sub(gaps);
+        let sizes = resolve(constraints, available);
+
+        let mut position = 0;
+        let mut spacing = vec![0; constraints.len()];
+        let free = available.saturating_sub(sizes.iter().sum());
+        match self.alignment {
+            Alignment::Start => {}
+            Alignment::Center => position = free / 2,
+            Alignment::End => position = free,
+            Alignment::SpaceBetween => distribute(&mut spacing[1..], free),
+            Alignment::SpaceEvenly => {
+                let mut around = vec![0; constraints.len() + 1];
+                distribute(&mut around, free);
+                position = around[0];
+                spacing[1..].copy_from_slice(&around[1..constraints.len()]);
+            }
+        }
+
+        let mut rects = Vec::with_capacity(constraints.len());
+        for (i, size) in sizes.iter().enumerate() {
+            if i > 0 {
+                position += self.gap as u32 + spacing[i];
+            }
+            let start = position.min(u16::MAX as u32) as u16;
+            let length = (*size).min(u16::MAX as u32) as u16;
+            rects.push(match self.direction {
+                Direction::Horizontal => {
+                    Rect::new(inner.x.saturating_add(start), inner.y, length, inner.height)
+                }
+                Direction::Vertical => {
+                    Rect::new(inner.x, inner.y.saturating_add(start), inner.width, length)
+                }
+            });
+            position += *size;
+        }
+        rects
+    }
+}
+
+/// Returns the size of each constraint within the available cells.
+fn resolve(constraints: &[Constraint], available: u32) -> Vec<u32> {
+    let mut sizes: Vec<u32> = constraints
+        .iter()
+        .map(|constraint| match *constraint {
+            Constraint::Length(length) => length as u32,
+            Constraint::Percentage(percentage) => available * percentage.min(100) as u32 / 100,
+            Constraint::Ratio(_, 0) => 0,
+            Constraint::Ratio(numerator, denominator) => {
+                (available as u64 * numerator.min(denominator) as u64 / denominator as u64) as u32
+            }
+            Constraint::Min(length) | Constraint::Max(length) => length as u32,
+            Constraint::Fill(_) => 0,
+        })
+        .collect();
+
+    let total: u32 = sizes.iter().sum();
+    if total > available {
+        let mut excess = total - available;
+        let priorities: [fn(&Constraint) -> bool; 3] = [
+            |constraint| {
+                matches!(
+                    constraint,
+                    Constraint::Max(_) | Constraint::Percentage(_) | Constraint::Ratio(..)
+                )
+            },
+            |constraint| matches!(constraint, Constraint::Length(_)),
+            |constraint| matches!(constraint, Constraint::Min(_)),
+        ];
+        for shrinks in priorities {
+            for (size, constraint) in sizes.iter_mut().zip(constraints).rev() {
+                if shrinks(constraint) {
+                    let cut = excess.min(*size);
+                    *size -= cut;
+                    excess -= cut;
+                }
+            }
+        }
+        return sizes;
+    }
+
+    let free = available - total;
+    let weights: Vec<u32> = constraints
+        .iter()
+        .map(|constraint| match constraint {
+            Constraint::Fill(weight) => *weight as u32,
+            _ => 0,
+        })
+        .collect();
+    let total_weight: u32 = weights.iter().sum();
+    if total_weight > 0 {
+        grow(&mut sizes, &weights, free);
+    } else {
+        let growing: Vec<usize> = (0..constraints.len())
+            .filter(|i| matches!(constraints[*i], Constraint::Min(_)))
+            .collect();
+        let mut shares = vec![0; growing.len()];
+        distribute(&mut shares, free);
+        for (i, share) in growing.into_iter().zip(shares) {
+            sizes[i] += share;
+        }
+    }
+    sizes
+}
+
+/// Splits the cells evenly, the first slots get the remainder.
+fn distribute(slots: &mut [u32], cells: u32) {
+    if slots.is_empty() {
+        return;
+    }
+    let share = cells / slots.len() as u32;
+    let remainder = (cells % slots.len() as u32) as usize;
+    for (i, slot) in slots.iter_mut().enumerate() {
+        *slot = share + (i < remainder) as u32;
+    }
+}
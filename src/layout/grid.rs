@@ -0,0 +1,262 @@
+use crate::layout::{grow, Padding, Rect};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Size of a grid column or row.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Track {
+    /// Exact number of cells.
+    Length(u16),
+    /// Share of the space left over, relative to the other fractions.
+    Fraction(u16),
+    /// Size of the largest child only spanning this track.
+    Auto,
+}
+
+/// Cells a child of a grid covers.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GridPlacement {
+    /// Cells starting at the column and row index, counted from 0.
+    Cells {
+        /// Index of the first column.
+        column: u16,
+        /// Index of the first row.
+        row: u16,
+        /// Number of columns covered.
+        column_span: u16,
+        /// Number of rows covered.
+        row_span: u16,
+    },
+    /// All cells of the named area, see [Grid::areas].
+    Area(String),
+}
+
+impl GridPlacement {
+    /// Places the child in a single cell.
+    pub fn cell(column: u16, row: u16) -> GridPlacement {
+        GridPlacement::span(column, row, 1, 1)
+    }
+
+    /// Places the child across several cells.
+    pub fn span(column: u16, row: u16, column_span: u16, row_span: u16) -> GridPlacement {
+        GridPlacement::Cells {
+            column,
+            row,
+            column_span,
+            row_span,
+        }
+    }
+
+    /// Places the child in a named area.
+    pub fn area(name: &str) -> GridPlacement {
+        GridPlacement::Area(name.to_string())
+    }
+}
+
+/// Places children in the cells of column and row tracks.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Grid {
+    /// Column sizes from left to right.
+    pub columns: Vec<Track>,
+    /// Row sizes from top to bottom.
+    pub rows: Vec<Track>,
+    /// Cells between columns.
+    pub column_gap: u16,
+    /// Cells between rows.
+    pub row_gap: u16,
+    /// Space left free inside the area.
+    pub padding: Padding,
+    /// Area names of the cells, one string per row with names separated by whitespace.
+    ///
+    /// Cells named "." are part of no area. Areas should be rectangular, otherwise they cover
+    /// the smallest rectangle around their cells.
+    pub areas: Vec<String>,
+}
+
+/// Start and size of the cells of a placement along one axis.
+type Span = (usize, usize);
+
+impl Grid {
+    /// Creates a grid with the given tracks and no gaps.
+    pub fn new(columns: Vec<Track>, rows: Vec<Track>) -> Grid {
+        Grid {
+            columns,
+            rows,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the area of each placement, none if it is outside of the grid or names an
+    /// unknown area.
+    ///
+    /// The content sizes, width and height per placement, size the [Track::Auto] tracks. If the
+    /// tracks don't fit, the last ones shrink first.
+    pub fn place(
+        &self,
+        area: Rect,
+        placements: &[GridPlacement],
+        content_sizes: &[(u16, u16)],
+    ) -> Vec<Option<Rect>> {
+        let inner = area.inner(self.padding);
+        let spans: Vec<Option<(Span, Span)>> = placements
+            .iter()
+            .map(|placement| self.resolve(placement))
+            .collect();
+        let content = |horizontal: bool| -> Vec<(Span, u16)> {
+            spans
+                .iter()
+                .zip(content_sizes)
+                .filter_map(|(spans, (width, height))| {
+                    let (columns, rows) = (*spans)?;
+                    Some(if horizontal {
+                        (columns, *width)
+                    } else {
+                        (rows, *height)
+                    })
+                })
+                .collect()
+        };
+        let columns = tracks(
+            &self.columns,
+            inner.x,
+            inner.width,
+            self.column_gap,
+            &content(true),
+        );
+        let rows = tracks(
+            &self.rows,
+            inner.y,
+            inner.height,
+            self.row_gap,
+            &content(false),
+        );
+        spans
+            .into_iter()
+            .map(|spans| {
+                let ((column, column_span), (row, row_span)) = spans?;
+                let (x, width) = cover(&columns, column, column_span);
+                let (y, height) = cover(&rows, row, row_span);
+                Some(Rect::new(x, y, width, height))
+            })
+            .collect()
+    }
+
+    /// Returns the columns and rows of the placement, clipped to the grid.
+    fn resolve(&self, placement: &GridPlacement) -> Option<(Span, Span)> {
+        let (column, row, column_span, row_span) = match placement {
+            GridPlacement::Cells {
+                column,
+                row,
+                column_span,
+                row_span,
+            } => (
+                *column as usize,
+                *row as usize,
+                (*column_span).max(1) as usize,
+                (*row_span).max(1) as usize,
+            ),
+            GridPlacement::Area(name) => {
+                let cells: Vec<(usize, usize)> = self
+                    .areas
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(row, names)| {
+                        names
+                            .split_whitespace()
+                            .enumerate()
+                            .filter(|(_, cell)| cell == name)
+                            .map(move |(column, _)| (column, row))
+                    })
+                    .collect();
+                let first_column = cells.iter().map(|cell| cell.0).min()?;
+                let last_column = cells.iter().map(|cell| cell.0).max()?;
+                let first_row = cells.iter().map(|cell| cell.1).min()?;
+                let last_row = cells.iter().map(|cell| cell.1).max()?;
+                (
+                    first_column,
+                    first_row,
+                    last_column - first_column + 1,
+                    last_row - first_row + 1,
+                )
+            }
+        };
+        if column >= self.columns.len() || row >= self.rows.len() {
+            return None;
+        }
+        Some((
+            (column, column_span.min(self.columns.len() - column)),
+            (row, row_span.min(self.rows.len() - row)),
+        ))
+    }
+}
+
+/// Returns the start and size of each track along one axis.
+fn tracks(
+    tracks: &[Track],
+    start: u16,
+    size: u16,
+    gap: u16,
+    content: &[(Span, u16)],
+) -> Vec<(u16, u16)> {
+    if tracks.is_empty() {
+        return Vec::new();
+    }
+    let gaps = gap as u32 * (tracks.len() as u32 - 1);
+    let available = (size as u32).saturating_sub(gaps);
+    let mut sizes: Vec<u32> = tracks
+        .iter()
+        .enumerate()
+        .map(|(i, track)| match track {
+            Track::Length(length) => *length as u32,
+            Track::Fraction(_) => 0,
+            Track::Auto => content
+                .iter()
+                .filter(|((first, span), _)| *first == i && *span == 1)
+                .map(|(_, size)| *size as u32)
+                .max()
+                .unwrap_or(0),
+        })
+        .collect();
+
+    let total: u32 = sizes.iter().sum();
+    if total > available {
+        let mut excess = total - available;
+        for size in sizes.iter_mut().rev() {
+            let cut = excess.min(*size);
+            *size -= cut;
+            excess -= cut;
+        }
+    } else {
+        let free = available - total;
+        let weights: Vec<u32> = tracks
+            .iter()
+            .map(|track| match track {
+                Track::Fraction(weight) => *weight as u32,
+                _ => 0,
+            })
+            .collect();
+        grow(&mut sizes, &weights, free);
+    }
+
+    let mut position = start as u32;
+    sizes
+        .into_iter()
+        .map(|size| {
+            let track = (position.min(u16::MAX as u32) as u16, size as u16);
+            position += size + gap as u32;
+            track
+        })
+        .collect()
+}
+
+/// Returns the start and size covering the tracks including the gaps between them.
+fn cover(tracks: &[(u16, u16)], first: usize, span: usize) -> (u16, u16) {
+    let (start, _) = tracks[first];
+    let (last_start, last_size) = tracks[first + span - 1];
+    (start, last_start.saturating_add(last_size) - start)
+}
@@ -0,0 +1,147 @@
+//! Computes element positions from the available space.
+//!
+//! Layouts split a [Rect] into smaller ones and hand them to their children with
+//! [Element::set_rect](crate::tui::Element::set_rect). They run on every render with the size
+//! of the frame, so the screen follows the terminal size.
+
+mod anchor;
+mod flex;
+mod grid;
+
+pub use anchor::{Anchor, Extent, Offset};
+pub use flex::{Alignment, Constraint, Direction, Flex};
+pub use grid::{Grid, GridPlacement, Track};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rectangular area of the screen.
+///
+/// Coordinates are terminal coordinates, starting at 1 in the top left corner.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Rect {
+    /// X position.
+    pub x: u16,
+    /// Y position.
+    pub y: u16,
+    /// Width.
+    pub width: u16,
+    /// Height.
+    pub height: u16,
+}
+
+impl Rect {
+    #[allow(missing_docs)]
+    pub const fn new(x: u16, y: u16, width: u16, height: u16) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the x position right of the area.
+    pub fn right(&self) -> u16 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// Returns the y position below the area.
+    pub fn bottom(&self) -> u16 {
+        self.y.saturating_add(self.height)
+    }
+
+    /// Returns whether the area has no cells.
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Returns whether the cell at the coordinates is inside the area.
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Returns the area with the padding removed from its sides.
+    pub fn inner(&self, padding: Padding) -> Rect {
+        let horizontal = padding.left.saturating_add(padding.right);
+        let vertical = padding.top.saturating_add(padding.bottom);
+        Rect {
+            x: self.x.saturating_add(padding.left),
+            y: self.y.saturating_add(padding.top),
+            width: self.width.saturating_sub(horizontal),
+            height: self.height.saturating_sub(vertical),
+        }
+    }
+
+    /// Returns the area covered by both, empty if they don't overlap.
+    pub fn intersection(&self, other: Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        Rect {
+            x,
+            y,
+            width: right.saturating_sub(x),
+            height: bottom.saturating_sub(y),
+        }
+    }
+}
+
+/// Space left free inside the sides of an area.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Padding {
+    /// Cells left free at the top.
+    pub top: u16,
+    /// Cells left free at the right.
+    pub right: u16,
+    /// Cells left free at the bottom.
+    pub bottom: u16,
+    /// Cells left free at the left.
+    pub left: u16,
+}
+
+impl Padding {
+    /// Creates a padding of the same size on all sides.
+    pub const fn uniform(padding: u16) -> Padding {
+        Padding {
+            top: padding,
+            right: padding,
+            bottom: padding,
+            left: padding,
+        }
+    }
+
+    /// Creates a padding with separate sizes for the top and bottom and the left and right.
+    pub const fn symmetric(vertical: u16, horizontal: u16) -> Padding {
+        Padding {
+            top: vertical,
+            right: horizontal,
+            bottom: vertical,
+            left: horizontal,
+        }
+    }
+}
+
+/// Adds the free cells to the sizes by weight, the first weighted sizes get the remainder.
+fn grow(sizes: &mut [u32], weights: &[u32], free: u32) {
+    let total_weight: u32 = weights.iter().sum();
+    if total_weight == 0 {
+        return;
+    }
+    let mut remaining = free;
+    for (size, weight) in sizes.iter_mut().zip(weights) {
+        let share = (free as u64 * *weight as u64 / total_weight as u64) as u32;
+        *size += share;
+        remaining -= share;
+    }
+    for (size, weight) in sizes.iter_mut().zip(weights) {
+        if remaining > 0 && *weight > 0 {
+            *size += 1;
+            remaining -= 1;
+        }
+    }
+}
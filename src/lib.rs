@@ -1,12 +1,24 @@
 //! Tui lib ^.^
 
+pub mod backend;
+pub mod buffer;
 pub mod colors;
+pub mod cursor;
 pub mod elements;
 pub mod errors;
+pub mod export;
 pub mod input;
+pub mod layout;
+pub mod recorder;
+pub mod rich_text;
+pub mod style;
+pub mod terminal;
+pub mod theme;
 pub mod tui;
+pub mod unicode;
 
 pub use colors::bg_color_to_string;
 pub use colors::fg_color_to_string;
 pub use colors::force_colors;
 pub use colors::Color;
+pub use style::Style;
@@ -0,0 +1,135 @@
+//! Session recording in the asciicast v2 format.
+//!
+//! Recordings can be replayed with `asciinema play` or embedded with the asciinema player. Attach
+//! a [Recorder] to a [WriterBackend](crate::backend::WriterBackend) to record the frames and
+//! optionally to the [Input](crate::input::Input) to record key presses and mouse events.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// State shared between all clones of a recorder.
+struct Recording {
+    /// Cast file the events are written to.
+    writer: Box<dyn Write + Send>,
+    /// Time the recording started, event times are relative to it.
+    start: Instant,
+    /// Whether events are still written.
+    active: bool,
+    /// Start of a multi byte character read from the terminal.
+    partial_input: Vec<u8>,
+}
+
+/// Writes output and input events to an asciicast v2 file.
+///
+/// Clones write to the same file.
+#[derive(Clone)]
+pub struct Recorder(Arc<Mutex<Recording>>);
+
+impl Recorder {
+    /// Starts a recording of a screen of the given size, writing the header right away.
+    pub fn new(
+        writer: impl Write + Send + 'static,
+        width: u16,
+        height: u16,
+    ) -> io::Result<Recorder> {
+        let mut writer: Box<dyn Write + Send> = Box::new(writer);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+        writeln!(
+            writer,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}}}",
+            width, height, timestamp
+        )?;
+        Ok(Recorder(Arc::new(Mutex::new(Recording {
+            writer,
+            start: Instant::now(),
+            active: true,
+            partial_input: Vec::new(),
+        }))))
+    }
+
+    /// Starts a recording into a new file at the path.
+    pub fn create(path: impl AsRef<Path>, width: u16, height: u16) -> io::Result<Recorder> {
+        Recorder::new(BufWriter::new(File::create(path)?), width, height)
+    }
+
+    /// Records data written to the terminal.
+    pub fn record_output(&self, data: &[u8]) -> io::Result<()> {
+        self.record("o", data)
+    }
+
+    /// Records data read from the terminal.
+    ///
+    /// Characters split across calls are recorded once complete.
+    pub fn record_input(&self, data: &[u8]) -> io::Result<()> {
+        let input = {
+            let mut recording = self.0.lock().unwrap_or_else(|e| e.into_inner());
+            let mut input = std::mem::take(&mut recording.partial_input);
+            input.extend_from_slice(data);
+            if let Err(error) = std::str::from_utf8(&input) {
+                if error.error_len().is_none() {
+                    recording.partial_input = input.split_off(error.valid_up_to());
+                }
+            }
+            input
+        };
+        self.record("i", &input)
+    }
+
+    /// Returns whether events are still recorded.
+    pub fn is_recording(&self) -> bool {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).active
+    }
+
+    /// Ends the recording and flushes the file, later events are ignored.
+    pub fn stop(&self) -> io::Result<()> {
+        let mut recording = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        recording.active = false;
+        recording.writer.flush()
+    }
+
+    /// Writes an event of the kind with the time since the start.
+    fn record(&self, kind: &str, data: &[u8]) -> io::Result<()> {
+        let mut recording = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if !recording.active || data.is_empty() {
+            return Ok(());
+        }
+        let time = recording.start.elapsed().as_secs_f64();
+        writeln!(
+            recording.writer,
+            "[{:.6}, \"{}\", \"{}\"]",
+            time,
+            kind,
+            escape_json(&String::from_utf8_lossy(data))
+        )
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+/// Escapes the text for use in a json string.
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            '\r' => escaped += "\\r",
+            '\t' => escaped += "\\t",
+            c if (c as u32) < 0x20 || c == '\x7f' => {
+                escaped += &format!("\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
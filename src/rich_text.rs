@@ -0,0 +1,193 @@
+//! Styled text made of spans and the markup used to write it.
+//!
+//! Markup tags are written in brackets and apply until they are closed by `[/]`:
+//! `[bold red]error[/]: file not found`. A tag lists attributes (`bold`, `dim`, `italic`,
+//! `underline`, `double_underline`, `curly_underline`, `dotted_underline`, `dashed_underline`,
+//! `blink`, `reverse`, `strikethrough`) and colors, a color after `on` sets the background.
+//! Colors use the formats accepted by [Color](crate::Color), like palette names or hex codes.
+//!
+//! Brackets that don't form a valid tag are kept as text, `[[` always prints `[`.
+
+use crate::style::{Style, Underline};
+use crate::unicode;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Part of a line sharing one style.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Span {
+    /// Text content.
+    pub text: String,
+    /// Style applied on top of the element style.
+    ///
+    /// Unset colors use the element colors.
+    pub style: Style,
+}
+
+impl Span {
+    #[allow(missing_docs)]
+    pub fn new(text: impl Into<String>, style: Style) -> Span {
+        Span {
+            text: text.into(),
+            style,
+        }
+    }
+}
+
+/// One line of styled text.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Line {
+    /// Spans in reading order.
+    pub spans: Vec<Span>,
+}
+
+impl Line {
+    /// Returns the text without styling.
+    pub fn get_plain(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Returns the number of cells the line takes up.
+    pub fn get_width(&self) -> usize {
+        self.spans
+            .iter()
+            .map(|span| unicode::width(&span.text))
+            .sum()
+    }
+
+    /// Appends the text, merging it into the last span if the style matches.
+    pub fn push(&mut self, text: &str, style: Style) {
+        if text.is_empty() {
+            return;
+        }
+        match self.spans.last_mut() {
+            Some(span) if span.style == style => span.text += text,
+            _ => self.spans.push(Span::new(text, style)),
+        }
+    }
+
+    /// Appends the spans of the other line.
+    pub fn append(&mut self, other: Line) {
+        for span in other.spans {
+            self.push(&span.text, span.style);
+        }
+    }
+
+    /// Splits the line at the last grapheme boundary that fits into the width.
+    pub fn split_at_width(&self, width: usize) -> (Line, Line) {
+        let mut start = Line::default();
+        let mut rest = Line::default();
+        let mut remaining = width;
+        for span in &self.spans {
+            if !rest.spans.is_empty() {
+                rest.push(&span.text, span.style);
+                continue;
+            }
+            let (fits, overflow) = unicode::split_at_width(&span.text, remaining);
+            start.push(fits, span.style);
+            rest.push(overflow, span.style);
+            remaining -= unicode::width(fits);
+        }
+        (start, rest)
+    }
+
+    /// Splits the line after the first grapheme.
+    pub fn split_first(&self) -> (Line, Line) {
+        let mut start = Line::default();
+        let mut rest = self.clone();
+        if let Some(span) = rest.spans.first_mut() {
+            let first = unicode::graphemes(&span.text).next().unwrap_or_default();
+            start.push(first, span.style);
+            span.text.replace_range(..first.len(), "");
+            if span.text.is_empty() {
+                rest.spans.remove(0);
+            }
+        }
+        (start, rest)
+    }
+}
+
+impl From<&str> for Line {
+    fn from(text: &str) -> Self {
+        let mut line = Line::default();
+        line.push(text, Style::default());
+        line
+    }
+}
+
+/// Returns the text as lines, parsing it as markup if enabled.
+pub fn to_lines(text: &str, markup: bool) -> Vec<Line> {
+    if markup {
+        parse_markup(text)
+    } else {
+        text.split('\n').map(Line::from).collect()
+    }
+}
+
+/// Parses markup into lines of spans, splitting at '\n'.
+pub fn parse_markup(markup: &str) -> Vec<Line> {
+    let mut lines = vec![Line::default()];
+    let mut styles = vec![Style::default()];
+    let mut rest = markup;
+    while let Some(c) = rest.chars().next() {
+        let style = *styles.last().unwrap();
+        if c == '\n' {
+            lines.push(Line::default());
+            rest = &rest[1..];
+            continue;
+        }
+        if c == '[' {
+            if let Some(escaped) = rest.strip_prefix("[[") {
+                lines.last_mut().unwrap().push("[", style);
+                rest = escaped;
+                continue;
+            }
+            if let Some((tag, after)) = rest[1..].split_once(']') {
+                if tag.starts_with('/') {
+                    if styles.len() > 1 {
+                        styles.pop();
+                    }
+                    rest = after;
+                    continue;
+                }
+                if let Some(tag_style) = parse_tag(tag) {
+                    styles.push(style.patch(tag_style));
+                    rest = after;
+                    continue;
+                }
+            }
+        }
+        lines.last_mut().unwrap().push(&rest[..c.len_utf8()], style);
+        rest = &rest[c.len_utf8()..];
+    }
+    lines
+}
+
+/// Parses the content of a markup tag, none if any part is unknown.
+fn parse_tag(tag: &str) -> Option<Style> {
+    let mut style = Style::default();
+    let mut words = tag.split_whitespace().peekable();
+    words.peek()?;
+    while let Some(word) = words.next() {
+        let attributes = &mut style.attributes;
+        match word.to_ascii_lowercase().as_str() {
+            "bold" => attributes.bold = true,
+            "dim" => attributes.dim = true,
+            "italic" => attributes.italic = true,
+            "underline" => attributes.underline = Underline::Single,
+            "double_underline" => attributes.underline = Underline::Double,
+            "curly_underline" => attributes.underline = Underline::Curly,
+            "dotted_underline" => attributes.underline = Underline::Dotted,
+            "dashed_underline" => attributes.underline = Underline::Dashed,
+            "blink" => attributes.blink = true,
+            "reverse" => attributes.reverse = true,
+            "strikethrough" => attributes.strikethrough = true,
+            "on" => style.bg_color = Some(words.next()?.parse().ok()?),
+            _ => style.fg_color = Some(word.parse().ok()?),
+        }
+    }
+    Some(style)
+}
@@ -0,0 +1,189 @@
+//! Text styling, colors combined with attributes.
+
+use crate::colors::{bg_color_to_string, fg_color_to_string, get_color_depth, Color};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Defines how text is underlined.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Underline {
+    /// No underline.
+    #[default]
+    None,
+    /// Straight line.
+    Single,
+    /// Two straight lines.
+    Double,
+    /// Wavy line, not supported by every terminal.
+    Curly,
+    /// Dotted line, not supported by every terminal.
+    Dotted,
+    /// Dashed line, not supported by every terminal.
+    Dashed,
+}
+
+/// Text attributes applied on top of the colors.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Attributes {
+    /// Bold or increased intensity.
+    pub bold: bool,
+    /// Faint or decreased intensity.
+    pub dim: bool,
+    /// Italic text.
+    pub italic: bool,
+    /// Underline kind.
+    pub underline: Underline,
+    /// Underline color, uses the foreground color if none.
+    pub underline_color: Option<Color>,
+    /// Blinking text.
+    pub blink: bool,
+    /// Swaps foreground and background colors.
+    pub reverse: bool,
+    /// Crossed out text.
+    pub strikethrough: bool,
+}
+
+impl Attributes {
+    /// Returns the attributes with the set attributes of the other applied on top.
+    pub fn patch(self, other: Attributes) -> Attributes {
+        Attributes {
+            bold: self.bold || other.bold,
+            dim: self.dim || other.dim,
+            italic: self.italic || other.italic,
+            underline: if other.underline == Underline::None {
+                self.underline
+            } else {
+                other.underline
+            },
+            underline_color: other.underline_color.or(self.underline_color),
+            blink: self.blink || other.blink,
+            reverse: self.reverse || other.reverse,
+            strikethrough: self.strikethrough || other.strikethrough,
+        }
+    }
+
+    /// Returns the ansi escape sequence enabling the attributes.
+    pub fn to_escape(&self) -> String {
+        let mut codes: Vec<&str> = Vec::new();
+        if self.bold {
+            codes.push("1");
+        }
+        if self.dim {
+            codes.push("2");
+        }
+        if self.italic {
+            codes.push("3");
+        }
+        codes.push(match self.underline {
+            Underline::None => "",
+            Underline::Single => "4",
+            Underline::Double => "4:2",
+            Underline::Curly => "4:3",
+            Underline::Dotted => "4:4",
+            Underline::Dashed => "4:5",
+        });
+        if self.blink {
+            codes.push("5");
+        }
+        if self.reverse {
+            codes.push("7");
+        }
+        if self.strikethrough {
+            codes.push("9");
+        }
+        codes.retain(|code| !code.is_empty());
+        let mut escape = if codes.is_empty() {
+            String::new()
+        } else {
+            format!("\x1b[{}m", codes.join(";"))
+        };
+        if let Some(color) = self.underline_color {
+            escape += &get_color_depth().underline_escape(color);
+        }
+        escape
+    }
+}
+
+/// Describes the look of text: colors and attributes.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Style {
+    /// Foreground color, terminal default if none.
+    pub fg_color: Option<Color>,
+    /// Background color, terminal default if none.
+    pub bg_color: Option<Color>,
+    /// Text attributes.
+    pub attributes: Attributes,
+}
+
+impl Style {
+    /// Creates a style with the given colors and no attributes.
+    pub fn new(fg_color: Option<Color>, bg_color: Option<Color>) -> Style {
+        Style {
+            fg_color,
+            bg_color,
+            attributes: Attributes::default(),
+        }
+    }
+
+    /// Returns the style with the attributes replaced.
+    pub fn with_attributes(self, attributes: Attributes) -> Style {
+        Style { attributes, ..self }
+    }
+
+    /// Returns the style with the set colors and attributes of the other applied on top.
+    pub fn patch(self, other: Style) -> Style {
+        Style {
+            fg_color: other.fg_color.or(self.fg_color),
+            bg_color: other.bg_color.or(self.bg_color),
+            attributes: self.attributes.patch(other.attributes),
+        }
+    }
+
+    /// Returns the style drawn over the other one, translucent colors are blended.
+    ///
+    /// The foreground is blended with the resulting background. An unset background keeps the
+    /// one below, a translucent background over the terminal default is used as is unless it is
+    /// fully transparent.
+    pub fn over(self, below: Style) -> Style {
+        let bg_color = match self.bg_color {
+            Some(color) => blend(color, below.bg_color),
+            None => below.bg_color,
+        };
+        let fg_color = match (self.fg_color, bg_color) {
+            (Some(color), Some(bg_color)) => Some(color.over(bg_color)),
+            (fg_color, _) => fg_color,
+        };
+        Style {
+            fg_color,
+            bg_color,
+            attributes: self.attributes,
+        }
+    }
+
+    /// Returns the ansi escape sequence forcing the style, unset colors use the terminal default.
+    pub fn to_escape(&self) -> String {
+        let mut escape = "\x1b[0m".to_string();
+        if let Some(color) = self.bg_color {
+            escape += &bg_color_to_string(color);
+        }
+        if let Some(color) = self.fg_color {
+            escape += &fg_color_to_string(color);
+        }
+        escape + &self.attributes.to_escape()
+    }
+}
+
+/// Returns the color drawn over the other one, none stands for the terminal default.
+pub fn blend(color: Color, below: Option<Color>) -> Option<Color> {
+    match below {
+        Some(below) => Some(color.over(below)),
+        None if color.a == 0 => None,
+        None => Some(color),
+    }
+}
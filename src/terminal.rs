@@ -0,0 +1,257 @@
+//! Terminal session handling.
+
+use libc::pid_t;
+use nix::sys::signal::{kill, SIGSTOP};
+use nix::sys::termios;
+use nix::sys::termios::Termios;
+use nix::unistd::Pid;
+use std::io::{self, Write};
+use std::panic;
+use std::process::id;
+use std::sync::{Mutex, Once};
+
+use crate::cursor::RESET_CURSOR_SHAPE;
+use crate::errors::SessionActiveError;
+use crate::tui::request_redraw;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// State needed to restore the terminal, shared with the panic hook.
+struct ReturnState {
+    /// Contains the terminal emulator state before the session started.
+    termios: Termios,
+    /// Options of the active session.
+    options: SessionOptions,
+}
+
+/// Return state of the active session, none if the terminal is not in tui mode.
+static RETURN_STATE: Mutex<Option<ReturnState>> = Mutex::new(None);
+
+/// Makes sure the panic hook is only installed once.
+static PANIC_HOOK: Once = Once::new();
+
+/// Describes how the terminal is set up for a session.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct SessionOptions {
+    /// Use raw mode instead of direct input, raw mode disables all input processing.
+    pub raw: bool,
+    /// Draw on the alternate screen, the previous terminal content returns once the session ends.
+    ///
+    /// Ignored for inline viewports.
+    pub alternate_screen: bool,
+    /// Part of the terminal used for drawing.
+    pub viewport: Viewport,
+}
+
+/// Describes the part of the terminal a tui draws on.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Viewport {
+    /// The whole terminal, coordinates start at the top left corner of the screen.
+    #[default]
+    Fullscreen,
+    /// The given number of lines below the cursor position the session started at.
+    ///
+    /// Coordinates start at the top left corner of the reserved lines, the last frame stays in
+    /// the scrollback once the session ends. Mouse tracking is not enabled.
+    Inline(u16),
+}
+
+impl Viewport {
+    /// Returns the escape sequence moving the cursor to the viewport coordinates.
+    pub fn move_cursor(&self, x: u16, y: u16) -> String {
+        match self {
+            Viewport::Fullscreen => format!("\x1b[{};{}H", y, x),
+            // restore the cursor saved at the top of the viewport and move relative to it
+            Viewport::Inline(_) if y > 1 => format!("\x1b8\x1b[{}B\x1b[{}G", y - 1, x),
+            Viewport::Inline(_) => format!("\x1b8\x1b[{}G", x),
+        }
+    }
+}
+
+/// Puts the terminal into tui mode and restores it when dropped.
+///
+/// A panic hook is installed that restores the terminal before the panic message is printed.
+pub struct Session {
+    /// Options the session was started with.
+    options: SessionOptions,
+}
+
+impl Session {
+    /// Starts a session, switching input mode and screen, hiding the cursor and enabling mouse
+    /// tracking.
+    ///
+    /// Fails if another session is active, it would restore the terminal to the wrong state.
+    pub fn new(options: SessionOptions) -> Result<Session, SessionActiveError> {
+        let mut return_state = RETURN_STATE.lock().unwrap_or_else(|e| e.into_inner());
+        if return_state.is_some() {
+            return Err(SessionActiveError);
+        }
+        PANIC_HOOK.call_once(|| {
+            let previous_hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                restore();
+                previous_hook(info);
+            }));
+        });
+
+        let termios = enter(options);
+        *return_state = Some(ReturnState { termios, options });
+
+        Ok(Session { options })
+    }
+
+    /// Returns the options the session was started with.
+    pub fn get_options(&self) -> SessionOptions {
+        self.options
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+/// Restores the terminal to the state before the session started.
+///
+/// Does nothing if there is no active session.
+pub fn restore() {
+    let Some(state) = RETURN_STATE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+    else {
+        return;
+    };
+    leave(&state);
+}
+
+/// Temporarily restores the terminal and stops the process, same as ctrl + z.
+///
+/// See [resume].
+pub fn suspend() {
+    if let Some(state) = RETURN_STATE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+    {
+        leave(state);
+    }
+    let _ = kill(Pid::from_raw(id() as pid_t), SIGSTOP);
+}
+
+/// Sets the terminal up again after the process was continued.
+pub fn resume() {
+    if let Some(state) = RETURN_STATE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+    {
+        match state.options.viewport {
+            Viewport::Fullscreen => {
+                enter(state.options);
+            }
+            Viewport::Inline(_) => {
+                // return to the saved top of the viewport, reserving lines again would move it
+                // down on every resume
+                set_input_mode(state.options);
+                print!("\x1b[0m\x1b8\x1b[?25l");
+                let _ = io::stdout().lock().flush();
+            }
+        }
+        request_redraw();
+    }
+}
+
+/// Switches to the input mode of the options, returns the previous emulator state.
+fn set_input_mode(options: SessionOptions) -> Termios {
+    if options.raw {
+        set_raw_mode()
+    } else {
+        set_direct_input()
+    }
+}
+
+/// Sets up the terminal for the options, returns the previous emulator state.
+fn enter(options: SessionOptions) -> Termios {
+    let termios = set_input_mode(options);
+    match options.viewport {
+        Viewport::Fullscreen => {
+            if options.alternate_screen {
+                print!("\x1b[?1049h");
+            }
+            print!("\x1b[0m\x1b[H\x1b[J\x1b[?25l\x1b[?1003h");
+        }
+        Viewport::Inline(height) => {
+            // scrolls the terminal if there is not enough space below the cursor
+            print!(
+                "\x1b[0m\r{}\x1b[{}A\x1b7\x1b[?25l",
+                "\n".repeat(height as usize),
+                height
+            );
+        }
+    }
+    let _ = io::stdout().lock().flush();
+    termios
+}
+
+/// Undoes everything done by [enter].
+fn leave(state: &ReturnState) {
+    set_mode(&state.termios);
+    match state.options.viewport {
+        Viewport::Fullscreen => {
+            print!(
+                "\x1b[0m\x1b[H\x1b[J{}\x1b[?25h\x1b[?1003l",
+                RESET_CURSOR_SHAPE
+            );
+            if state.options.alternate_screen {
+                print!("\x1b[?1049l");
+            }
+        }
+        Viewport::Inline(height) => {
+            // leave the last frame in place and continue below it
+            print!(
+                "\x1b[0m{}\r\n{}\x1b[?25h",
+                Viewport::Inline(height).move_cursor(1, height.max(1)),
+                RESET_CURSOR_SHAPE
+            );
+        }
+    }
+    let _ = io::stdout().lock().flush();
+}
+
+/// Enables the emulator raw mode, returns the previous state.
+///
+/// Completly disables input processing.
+///
+/// Some codes are different to direct input.
+fn set_raw_mode() -> Termios {
+    let mut tio = termios::tcgetattr(0).expect("Unable to get terminal attribute!");
+    let old = tio.clone();
+    termios::cfmakeraw(&mut tio);
+    termios::tcsetattr(0, termios::SetArg::TCSANOW, &tio).unwrap();
+    old
+}
+
+/// Enables the emulator direct input mode, returns the previous state.
+///
+/// Preffered to raw mode as it doesnt disable input processing.
+///
+/// Some codes are different to raw mode.
+fn set_direct_input() -> Termios {
+    let mut tio = termios::tcgetattr(0).expect("Unable to get terminal attribute!");
+    let old = tio.clone();
+    tio.input_flags.insert(termios::InputFlags::BRKINT);
+    tio.input_flags.remove(termios::InputFlags::IGNBRK);
+    tio.local_flags.remove(termios::LocalFlags::ECHO);
+    tio.local_flags.remove(termios::LocalFlags::ICANON);
+    termios::tcsetattr(0, termios::SetArg::TCSANOW, &tio).unwrap();
+    old
+}
+
+/// Set mode of terminal emulator.
+fn set_mode(attr: &Termios) {
+    let _ = termios::tcsetattr(0, termios::SetArg::TCSANOW, attr);
+}
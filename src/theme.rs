@@ -0,0 +1,169 @@
+//! Semantic colors shared by all elements.
+//!
+//! Elements use the active theme for every color they leave as none, without a theme the
+//! terminal default colors are used.
+
+use crate::colors::{
+    Color, BLACK, GREEN, GREEN_50, GREY, LIGHT_GREY, LIGHT_GREY_50, LIGHT_GREY_75, ORANGE,
+    ORANGE_50, ORANGE_75, RED, RED_75, WHITE, YELLOW,
+};
+use std::sync::RwLock;
+
+#[cfg(feature = "serde")]
+use crate::errors::LoadThemeError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::{fs, path::Path};
+
+/// Semantic color roles of a theme.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Role {
+    /// Background of the screen and elements.
+    Surface,
+    /// Regular text.
+    Text,
+    /// Highlights like icons.
+    Accent,
+    /// Background of selected elements.
+    Selected,
+    /// Text of selected elements.
+    SelectedText,
+    /// Text of disabled elements.
+    Disabled,
+    /// Lines and outlines.
+    Border,
+    /// Errors.
+    Error,
+    /// Warnings.
+    Warning,
+    /// Success messages.
+    Success,
+}
+
+/// Colors for each semantic role.
+///
+/// Missing roles of a loaded theme are taken from the dark theme.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Theme {
+    /// Background of the screen and elements.
+    pub surface: Color,
+    /// Regular text.
+    pub text: Color,
+    /// Highlights like icons.
+    pub accent: Color,
+    /// Background of selected elements.
+    pub selected: Color,
+    /// Text of selected elements.
+    pub selected_text: Color,
+    /// Text of disabled elements.
+    pub disabled: Color,
+    /// Lines and outlines.
+    pub border: Color,
+    /// Errors.
+    pub error: Color,
+    /// Warnings.
+    pub warning: Color,
+    /// Success messages.
+    pub success: Color,
+}
+
+impl Theme {
+    /// Light text on a dark background.
+    pub const fn dark() -> Theme {
+        Theme {
+            surface: BLACK,
+            text: LIGHT_GREY,
+            accent: ORANGE,
+            selected: ORANGE_50,
+            selected_text: WHITE,
+            disabled: LIGHT_GREY_50,
+            border: GREY,
+            error: RED,
+            warning: YELLOW,
+            success: GREEN,
+        }
+    }
+
+    /// Dark text on a light background.
+    pub const fn light() -> Theme {
+        Theme {
+            surface: WHITE,
+            text: BLACK,
+            accent: ORANGE_75,
+            selected: ORANGE,
+            selected_text: BLACK,
+            disabled: LIGHT_GREY_50,
+            border: LIGHT_GREY_75,
+            error: RED_75,
+            warning: ORANGE_75,
+            success: GREEN_50,
+        }
+    }
+
+    /// Pure black and white with saturated highlights.
+    pub const fn high_contrast() -> Theme {
+        Theme {
+            surface: Color::new(0, 0, 0),
+            text: Color::new(255, 255, 255),
+            accent: Color::new(255, 255, 0),
+            selected: Color::new(255, 255, 0),
+            selected_text: Color::new(0, 0, 0),
+            disabled: Color::new(0xC0, 0xC0, 0xC0),
+            border: Color::new(255, 255, 255),
+            error: Color::new(255, 0x40, 0x40),
+            warning: Color::new(255, 0xA0, 0),
+            success: Color::new(0, 255, 0),
+        }
+    }
+
+    /// Returns the color of the role.
+    pub fn get(&self, role: Role) -> Color {
+        match role {
+            Role::Surface => self.surface,
+            Role::Text => self.text,
+            Role::Accent => self.accent,
+            Role::Selected => self.selected,
+            Role::SelectedText => self.selected_text,
+            Role::Disabled => self.disabled,
+            Role::Border => self.border,
+            Role::Error => self.error,
+            Role::Warning => self.warning,
+            Role::Success => self.success,
+        }
+    }
+
+    /// Loads a theme from a json file.
+    #[cfg(feature = "serde")]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Theme, LoadThemeError> {
+        let content = fs::read_to_string(path).map_err(LoadThemeError::Io)?;
+        serde_json::from_str(&content).map_err(LoadThemeError::Parse)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+/// Theme used by all elements, none uses the terminal default colors.
+static THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+/// Returns the active theme.
+pub fn get_theme() -> Option<Theme> {
+    *THEME.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Switches the active theme, applies on the next update.
+pub fn set_theme(theme: Option<Theme>) {
+    *THEME.write().unwrap_or_else(|e| e.into_inner()) = theme;
+}
+
+/// Returns the color, or the color of the role in the active theme if none.
+pub fn themed(color: Option<Color>, role: Role) -> Option<Color> {
+    color.or_else(|| get_theme().map(|theme| theme.get(role)))
+}
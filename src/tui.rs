@@ -1,23 +1,39 @@
 //! Tui handling module.
 use std::io;
-use std::io::Write;
-use std::sync::{Arc, RwLock};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
+use crate::backend::{Backend, WriterBackend};
+use crate::buffer::Buffer;
+use crate::cursor::Cursor;
+use crate::export::{export, ExportFormat};
 use crate::input::observers::{TuiKeyObserver, TuiMouseObserver};
-use crate::input::{KeyEvent, MouseEvent};
+use crate::input::{KeyEvent, MouseEvent, ResizeEvent};
+use crate::layout::Rect;
+use crate::style::Style;
+use crate::theme::get_theme;
 pub type RwLockElement = Arc<RwLock<dyn Element>>;
 pub type RwLockReactive = Arc<RwLock<dyn Reactive>>;
 
 /// Used for all tui elements.
 pub trait Element: Sync + Send {
-    /// Prints/renders the element.
-    fn print(&self);
+    /// Prints/renders the element into the buffer.
+    fn print(&self, buffer: &mut Buffer);
     /// Gets the z pos.
     fn get_z(&self) -> u16;
     /// Get visibility.
     fn get_visible(&self) -> bool;
     /// Set visibility.
     fn set_visible(&mut self, visible: bool);
+    /// Moves and resizes the element to fill the area, used by layouts.
+    ///
+    /// Elements without a size only move, others ignore it.
+    fn set_rect(&mut self, _rect: Rect) {}
+    /// Returns the area the element covers, none if it has no size.
+    fn get_rect(&self) -> Option<Rect> {
+        None
+    }
 }
 
 /// Used for reactive tui elements.
@@ -40,6 +56,10 @@ pub trait Reactive: Sync + Send {
     fn get_enabled(&self) -> bool;
     /// Set actionability.
     fn set_enabled(&mut self, enabled: bool);
+    /// Gets the cursor the element wants shown, usually only while selected.
+    fn get_cursor(&self) -> Option<Cursor> {
+        None
+    }
 }
 
 /// Element and reactive element group.
@@ -66,28 +86,168 @@ impl Group {
     }
 }
 
+/// Incremented each time the terminal contents are lost, see [request_redraw].
+static REDRAW_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Forces all renderers to redraw every cell on their next frame.
+///
+/// Used after the terminal was cleared outside of the renderer.
+pub fn request_redraw() {
+    REDRAW_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Keeps track of the frame currently displayed by a backend.
+pub struct Renderer {
+    /// Output frames are drawn to.
+    backend: Box<dyn Backend>,
+    /// Last drawn frame.
+    previous: Option<Buffer>,
+    /// Redraw generation the previous frame was drawn in.
+    generation: usize,
+}
+
+impl Default for Renderer {
+    /// Creates a renderer drawing to stdout.
+    fn default() -> Self {
+        Renderer::new(Box::new(WriterBackend::stdout()))
+    }
+}
+
+impl Renderer {
+    #[allow(missing_docs)]
+    pub fn new(backend: Box<dyn Backend>) -> Renderer {
+        Renderer {
+            backend,
+            previous: None,
+            generation: 0,
+        }
+    }
+
+    /// Returns the backend.
+    pub fn get_backend(&self) -> &dyn Backend {
+        self.backend.as_ref()
+    }
+
+    /// Returns the mutable backend.
+    pub fn get_backend_mut(&mut self) -> &mut dyn Backend {
+        self.backend.as_mut()
+    }
+
+    /// Replaces the backend, the next frame is drawn completely.
+    pub fn set_backend(&mut self, backend: Box<dyn Backend>) {
+        self.backend = backend;
+        self.invalidate();
+    }
+
+    /// Draws the frame, only emitting cells that changed since the last frame.
+    pub fn draw(&mut self, buffer: Buffer) -> io::Result<()> {
+        self.draw_with_cursor(buffer, None)
+    }
+
+    /// Draws the frame and places the cursor afterwards, hides it if none.
+    pub fn draw_with_cursor(&mut self, buffer: Buffer, cursor: Option<Cursor>) -> io::Result<()> {
+        let generation = REDRAW_GENERATION.load(Ordering::SeqCst);
+        let full_frame = self.backend.take_full_frame();
+        match &self.previous {
+            Some(previous) if self.generation == generation && !full_frame => {
+                self.backend.draw(&previous.diff(&buffer))?
+            }
+            _ => self.backend.draw(&buffer.content())?,
+        }
+        self.backend.set_cursor(cursor)?;
+        self.backend.flush()?;
+        self.previous = Some(buffer);
+        self.generation = generation;
+        Ok(())
+    }
+
+    /// Forgets the last frame so the next one is drawn completely.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+}
+
+/// Prints the visible elements into the buffer, ordered by their z position.
+///
+/// Elements with an area, see [Element::get_rect], can only write inside of it.
+pub fn print_elements(buffer: &mut Buffer, mut elements: Vec<RwLockElement>) {
+    elements.sort_by(|a, b| {
+        let a_z = a.read().unwrap().get_z();
+        let b_z = b.read().unwrap().get_z();
+        a_z.cmp(&b_z)
+    });
+    for element in elements {
+        let element_lock = element.read().unwrap();
+        if !element_lock.get_visible() {
+            continue;
+        }
+        match element_lock.get_rect() {
+            Some(rect) => buffer.with_clip(rect, |buffer| element_lock.print(buffer)),
+            None => element_lock.print(buffer),
+        }
+    }
+}
+
 /// Trait that defines behaviour shared between tui contexts.
 pub trait TUI {
-    /// Prints all elements.
-    fn update(&self) {
-        let mut sorted_elements = self.get_elements();
-        sorted_elements.sort_by(|a, b| {
-            let a_z = a.read().unwrap().get_z();
-            let b_z = b.read().unwrap().get_z();
-            a_z.cmp(&b_z)
-        });
-        for element in sorted_elements {
-            let element_lock = element.read().unwrap();
-            if element_lock.get_visible() {
-                element_lock.print();
+    /// Renders all visible elements into a new buffer.
+    fn render(&self, width: u16, height: u16) -> Buffer {
+        let mut buffer = Buffer::new(width, height);
+        if let Some(theme) = get_theme() {
+            for y in 1..=height {
+                buffer.set_string(
+                    1,
+                    y,
+                    &" ".repeat(width as usize),
+                    Style::new(None, Some(theme.surface)),
+                );
             }
-            drop(element_lock);
-            let _ = io::stdout().lock().flush();
         }
+        print_elements(&mut buffer, self.get_elements());
+        buffer
+    }
+
+    /// Prints all elements, only changed cells are written to the backend.
+    fn update(&self) {
+        let mut renderer = self.get_renderer().lock().unwrap();
+        let (width, height) = renderer.get_backend().get_size();
+        let _ = renderer.draw_with_cursor(self.render(width, height), self.get_cursor());
+    }
+
+    /// Returns the size of the screen as width and height.
+    fn get_size(&self) -> (u16, u16) {
+        self.get_renderer().lock().unwrap().get_backend().get_size()
+    }
+
+    /// Called after the terminal was resized, draws the whole screen in the new size.
+    ///
+    /// Layouts adapt to the new size on their own, override this to react otherwise.
+    fn handle_resize(&self, _event: ResizeEvent) {
+        self.get_renderer().lock().unwrap().invalidate();
+        self.update();
+    }
+
+    /// Renders the frame the backend currently shows, or will show on the next update.
+    fn screenshot(&self) -> Buffer {
+        let (width, height) = self.get_size();
+        self.render(width, height)
+    }
+
+    /// Writes a screenshot to the file, see [export](crate::export).
+    fn save_screenshot(&self, path: &Path, format: ExportFormat) -> io::Result<()> {
+        std::fs::write(path, export(&self.screenshot(), format))
+    }
+
+    /// Returns the cursor to show after drawing, hidden if none.
+    fn get_cursor(&self) -> Option<Cursor> {
+        None
     }
 
     /// Returns a copy of it's elements.
     fn get_elements(&self) -> Vec<RwLockElement>;
+
+    /// Returns the renderer keeping track of the displayed frame.
+    fn get_renderer(&self) -> &Mutex<Renderer>;
 }
 
 /// Contains main context of the reactive tui.
@@ -102,6 +262,8 @@ pub struct ReactiveTUI {
     pub selection_next: u8,
     /// Key event used to reduce the selection index
     pub selection_previous: u8,
+    /// Renderer keeping track of the displayed frame.
+    pub renderer: Mutex<Renderer>,
 }
 
 impl ReactiveTUI {
@@ -113,6 +275,23 @@ impl ReactiveTUI {
         Arc<RwLock<ReactiveTUI>>,
         Arc<TuiKeyObserver>,
         Arc<TuiMouseObserver>,
+    ) {
+        ReactiveTUI::with_backend(
+            selection_next,
+            selection_previous,
+            Box::new(WriterBackend::stdout()),
+        )
+    }
+
+    /// Creates a reactive tui drawing to the given backend.
+    pub fn with_backend(
+        selection_next: u8,
+        selection_previous: u8,
+        backend: Box<dyn Backend>,
+    ) -> (
+        Arc<RwLock<ReactiveTUI>>,
+        Arc<TuiKeyObserver>,
+        Arc<TuiMouseObserver>,
     ) {
         let tui = Arc::new(RwLock::new(ReactiveTUI {
             elements: Vec::new(),
@@ -120,32 +299,64 @@ impl ReactiveTUI {
             selected_element: 0,
             selection_next,
             selection_previous,
+            renderer: Mutex::new(Renderer::new(backend)),
         }));
         let key_observer = Arc::new(TuiKeyObserver { tui: tui.clone() });
         let mouse_observer = Arc::new(TuiMouseObserver { tui: tui.clone() });
         (tui, key_observer, mouse_observer)
     }
+
+    /// Returns the selected reactive element, it receives the key events.
+    ///
+    /// The selection index counts enabled elements only.
+    pub fn get_selected(&self) -> Option<RwLockReactive> {
+        let enabled: Vec<&RwLockReactive> = self
+            .reactive_elements
+            .iter()
+            .filter(|element| element.read().unwrap().get_enabled())
+            .collect();
+        if enabled.is_empty() {
+            return None;
+        }
+        Some(enabled[self.selected_element % enabled.len()].clone())
+    }
 }
 
 impl TUI for ReactiveTUI {
     fn get_elements(&self) -> Vec<RwLockElement> {
         self.elements.clone()
     }
+
+    /// Returns the cursor of the selected reactive element.
+    fn get_cursor(&self) -> Option<Cursor> {
+        self.get_selected()?.read().unwrap().get_cursor()
+    }
+
+    fn get_renderer(&self) -> &Mutex<Renderer> {
+        &self.renderer
+    }
 }
 
 /// Contains main context of the static tui.
 pub struct StaticTUI {
     #[allow(missing_docs)]
     pub elements: Vec<RwLockElement>,
+    /// Renderer keeping track of the displayed frame.
+    pub renderer: Mutex<Renderer>,
 }
 
 impl StaticTUI {
     #[allow(missing_docs)]
     pub fn new() -> Arc<RwLock<StaticTUI>> {
-        let tui = Arc::new(RwLock::new(StaticTUI {
+        StaticTUI::with_backend(Box::new(WriterBackend::stdout()))
+    }
+
+    /// Creates a static tui drawing to the given backend.
+    pub fn with_backend(backend: Box<dyn Backend>) -> Arc<RwLock<StaticTUI>> {
+        Arc::new(RwLock::new(StaticTUI {
             elements: Vec::new(),
-        }));
-        tui
+            renderer: Mutex::new(Renderer::new(backend)),
+        }))
     }
 }
 
@@ -153,4 +364,8 @@ impl TUI for StaticTUI {
     fn get_elements(&self) -> Vec<RwLockElement> {
         self.elements.clone()
     }
+
+    fn get_renderer(&self) -> &Mutex<Renderer> {
+        &self.renderer
+    }
 }
@@ -0,0 +1,39 @@
+//! Display width aware text measurement.
+//!
+//! Text is handled as grapheme clusters, wide characters take up two cells and combining marks
+//! none.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Returns the number of cells the grapheme cluster takes up, 0 to 2.
+pub fn grapheme_width(grapheme: &str) -> usize {
+    grapheme.width().min(2)
+}
+
+/// Returns the number of cells the text takes up.
+pub fn width(text: &str) -> usize {
+    graphemes(text).map(grapheme_width).sum()
+}
+
+/// Returns the grapheme clusters of the text.
+pub fn graphemes(text: &str) -> impl Iterator<Item = &str> {
+    text.graphemes(true)
+}
+
+/// Splits the text at the last grapheme boundary that fits into the width.
+pub fn split_at_width(text: &str, width: usize) -> (&str, &str) {
+    let mut used = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        used += grapheme_width(grapheme);
+        if used > width {
+            return text.split_at(index);
+        }
+    }
+    (text, "")
+}
+
+/// Returns the longest start of the text that fits into the width.
+pub fn truncate(text: &str, width: usize) -> &str {
+    split_at_width(text, width).0
+}
@@ -0,0 +1,59 @@
+use charflow::input::capabilities::ReplyParser;
+use charflow::Color;
+
+fn parse(input: &[u8]) -> (charflow::input::capabilities::Capabilities, Vec<u8>) {
+    let mut parser = ReplyParser::new();
+    for byte in input {
+        parser.feed(*byte);
+    }
+    parser.finish()
+}
+
+#[test]
+fn replies_are_separated_from_input() {
+    let (capabilities, passthrough) = parse(
+        b"x\x1b]11;rgb:0000/0000/ffff\x1b\\\x1b[?2026;2$y\x1b[Ay\x1bP>|kitty(0.31)\x1b\\\x1b[?62;4;52c\x1b[B",
+    );
+    assert_eq!(passthrough, b"x\x1b[Ay\x1b[B");
+    assert!(capabilities.responded);
+    assert!(capabilities.synchronized_output);
+    assert!(!capabilities.sgr_mouse);
+    assert!(capabilities.sixel);
+    assert!(capabilities.clipboard);
+    assert!(capabilities.truecolor);
+    assert_eq!(capabilities.background, Some(Color::new(0, 0, 255)));
+    assert_eq!(capabilities.foreground, None);
+    assert_eq!(capabilities.name.as_deref(), Some("kitty(0.31)"));
+    assert_eq!(capabilities.device_attributes, vec![62, 4, 52]);
+}
+
+#[test]
+fn unanswered_queries_leave_everything_unknown() {
+    let (capabilities, passthrough) = parse(b"q\x1bx\x1b[?1006;0$y\x1b]10;rgb:ff/80/0\x07");
+    assert_eq!(passthrough, b"q\x1bx");
+    assert!(!capabilities.responded);
+    assert!(!capabilities.sgr_mouse);
+    assert_eq!(capabilities.foreground, Some(Color::new(255, 128, 0)));
+}
+
+#[test]
+fn late_replies_are_filtered_while_streaming() {
+    let mut parser = ReplyParser::new();
+    for byte in b"a\x1b[?2026;1$y\x1b" {
+        parser.feed(*byte);
+    }
+    // the escape could start a reply, it is held back until more input arrives
+    assert_eq!(parser.take_passthrough(), b"a");
+    assert!(parser.is_pending());
+    parser.flush();
+    assert_eq!(parser.take_passthrough(), b"\x1b");
+
+    for byte in b"\x1b[?62c\x1b[A" {
+        parser.feed(*byte);
+    }
+    assert!(parser.is_done());
+    assert_eq!(parser.take_passthrough(), b"\x1b[A");
+    let capabilities = parser.get_capabilities();
+    assert!(capabilities.synchronized_output);
+    assert!(capabilities.responded);
+}
@@ -0,0 +1,137 @@
+use charflow::colors::{
+    detect_color_depth_with, ColorDepth, BLACK, GREY, ORANGE, ORANGE_50, WHITE, YELLOW,
+};
+use charflow::Color;
+use std::collections::HashMap;
+
+#[test]
+fn colors_map_to_nearest_palette_entry() {
+    assert_eq!(Color::new(0, 0, 0).to_ansi256(), 16);
+    assert_eq!(Color::new(255, 255, 255).to_ansi256(), 231);
+    assert_eq!(BLACK.to_ansi256(), 234);
+    assert_eq!(GREY.to_ansi256(), 237);
+    assert_eq!(ORANGE.to_ansi256(), 214);
+    assert_eq!(Color::new(255, 0, 0).to_ansi256(), 196);
+
+    assert_eq!(BLACK.to_ansi16(), 0);
+    assert_eq!(WHITE.to_ansi16(), 15);
+    assert_eq!(YELLOW.to_ansi16(), 3);
+    assert_eq!(Color::new(200, 10, 0).to_ansi16(), 1);
+}
+
+#[test]
+fn escapes_follow_color_depth() {
+    assert_eq!(
+        ColorDepth::TrueColor.fg_escape(ORANGE),
+        "\x1b[38;2;255;176;0m"
+    );
+    assert_eq!(ColorDepth::Ansi256.fg_escape(ORANGE), "\x1b[38;5;214m");
+    assert_eq!(ColorDepth::Ansi256.bg_escape(ORANGE), "\x1b[48;5;214m");
+    assert_eq!(ColorDepth::Ansi16.fg_escape(YELLOW), "\x1b[33m");
+    assert_eq!(ColorDepth::Ansi16.fg_escape(WHITE), "\x1b[97m");
+    assert_eq!(ColorDepth::Ansi16.bg_escape(BLACK), "\x1b[40m");
+    assert_eq!(ColorDepth::Monochrome.fg_escape(ORANGE), "");
+    assert_eq!(ColorDepth::Monochrome.underline_escape(ORANGE), "");
+}
+
+#[test]
+fn depth_is_detected_from_the_environment() {
+    let detect = |vars: &[(&str, &str)]| {
+        let mut vars: HashMap<&str, &str> = vars.iter().copied().collect();
+        // no terminfo entries are found
+        vars.insert("TERMINFO", "/nonexistent");
+        vars.insert("HOME", "/nonexistent");
+        detect_color_depth_with(|name| vars.get(name).map(|value| value.to_string()))
+    };
+    assert_eq!(
+        detect(&[("COLORTERM", "truecolor"), ("TERM", "xterm")]),
+        ColorDepth::TrueColor
+    );
+    assert_eq!(detect(&[("TERM", "xterm")]), ColorDepth::Ansi16);
+    assert_eq!(detect(&[("TERM", "xterm-256color")]), ColorDepth::Ansi256);
+    assert_eq!(detect(&[("TERM", "dumb")]), ColorDepth::Monochrome);
+    assert_eq!(detect(&[]), ColorDepth::Monochrome);
+    assert_eq!(detect(&[("TERM", "xterm-direct")]), ColorDepth::TrueColor);
+    assert_eq!(
+        detect(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")]),
+        ColorDepth::Monochrome
+    );
+}
+
+#[test]
+fn colors_parse_and_format() {
+    assert_eq!("#ffcc00".parse(), Ok(YELLOW));
+    assert_eq!("#FC0".parse(), Ok(YELLOW));
+    assert_eq!("rgb(255, 204, 0)".parse(), Ok(YELLOW));
+    // css names shadow the palette constants of the same name
+    assert_eq!("Yellow".parse(), Ok(Color::new(0xFF, 0xFF, 0x00)));
+    assert_eq!("black".parse(), Ok(Color::new(0, 0, 0)));
+    assert_eq!("orange_50".parse(), Ok(ORANGE_50));
+    assert_eq!("rebeccapurple".parse(), Ok(Color::new(0x66, 0x33, 0x99)));
+    assert!("#ffcc0".parse::<Color>().is_err());
+    assert!("#+f+f+f".parse::<Color>().is_err());
+    assert!("#-1f".parse::<Color>().is_err());
+    assert!("rgb(1, 2)".parse::<Color>().is_err());
+    assert!("no color".parse::<Color>().is_err());
+    assert_eq!(ORANGE.to_string(), "#ffb000");
+}
+
+#[test]
+fn hsl_and_hsv_round_trip() {
+    let color = Color::new(0x33, 0x99, 0xCC);
+    let (hue, saturation, lightness) = color.to_hsl();
+    assert_eq!(hue.round(), 200.0);
+    assert_eq!((saturation * 100.0).round(), 60.0);
+    assert_eq!((lightness * 100.0).round(), 50.0);
+    assert_eq!(Color::from_hsl(hue, saturation, lightness), color);
+    let (hue, saturation, value) = color.to_hsv();
+    assert_eq!((value * 100.0).round(), 80.0);
+    assert_eq!(Color::from_hsv(hue, saturation, value), color);
+    assert_eq!(Color::from_hsl(0.0, 1.0, 0.5), Color::new(255, 0, 0));
+}
+
+#[test]
+fn colors_are_adjusted_and_mixed() {
+    let black = Color::new(0, 0, 0);
+    let white = Color::new(255, 255, 255);
+    assert_eq!(black.mix(white, 0.5), Color::new(128, 128, 128));
+    assert_eq!(black.lighten(1.0), white);
+    assert_eq!(white.darken(0.5), Color::new(128, 128, 128));
+}
+
+#[test]
+fn contrast_follows_wcag() {
+    let black = Color::new(0, 0, 0);
+    let white = Color::new(255, 255, 255);
+    assert_eq!(black.contrast_ratio(white).round(), 21.0);
+    assert_eq!(YELLOW.readable_foreground(), BLACK);
+    assert_eq!(GREY.readable_foreground(), WHITE);
+    assert_eq!(black.best_contrast(&[GREY, YELLOW]), Some(YELLOW));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn colors_serialize_as_hex() {
+    assert_eq!(serde_json::to_string(&YELLOW).unwrap(), "\"#ffcc00\"");
+    assert_eq!(
+        serde_json::from_str::<Color>("\"rgb(255, 204, 0)\"").unwrap(),
+        YELLOW
+    );
+    assert!(serde_json::from_str::<Color>("\"nope\"").is_err());
+}
+
+#[test]
+fn alpha_is_parsed_and_blended() {
+    let black = Color::new(0, 0, 0);
+    let white = Color::new(255, 255, 255);
+    assert_eq!("#ffcc0080".parse(), Ok(YELLOW.with_alpha(0x80)));
+    assert_eq!("#fc08".parse(), Ok(YELLOW.with_alpha(0x88)));
+    assert_eq!("rgba(255, 204, 0, 0.5)".parse(), Ok(YELLOW.with_alpha(128)));
+    assert_eq!("transparent".parse(), Ok(Color::rgba(0, 0, 0, 0)));
+    assert_eq!(YELLOW.with_alpha(0x80).to_string(), "#ffcc0080");
+
+    assert_eq!(white.with_alpha(128).over(black), Color::new(128, 128, 128));
+    assert_eq!(white.over(black), white);
+    assert_eq!(Color::rgba(0, 0, 0, 0).over(white), white);
+    assert_eq!(white.fade(0.5).a, 128);
+}
@@ -0,0 +1,283 @@
+use charflow::backend::TestBackend;
+use charflow::elements::complex::{Container, ScrollView};
+use charflow::elements::simple::line_box::LINES_LIGHT;
+use charflow::elements::simple::{Box as LineBox, Interactable, Text, TextBox};
+use charflow::input::callbacks::{
+    ARROW_DOWN, CONTROL_SEQUENCE, ESCAPE, LOWERCASE_A, MOUSE_LEFT_DRAG, MOUSE_LEFT_PRESS,
+    MOUSE_RELEASE, MOUSE_WHEEL_UP, PAGE_DOWN, SEQUENCE_END,
+};
+use charflow::input::{
+    KeyAction, KeyEvent, KeyEventObserver, MouseAction, MouseEvent, MouseEventObserver,
+};
+use charflow::layout::Rect;
+use charflow::style::Attributes;
+use charflow::tui::{Reactive, ReactiveTUI, StaticTUI, TUI};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Serializes the tests dispatching mouse events through the tui, the scroll view notices
+/// those of all tests.
+static MOUSE_EVENTS: Mutex<()> = Mutex::new(());
+
+fn text(x: u16, y: u16, text: &str) -> Text {
+    Text {
+        x,
+        y,
+        z: 0,
+        text_color: None,
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        text: text.to_string(),
+        markup: false,
+    }
+}
+
+#[test]
+fn children_are_relative_and_clipped() {
+    let mut inner = Container::new(Rect::new(2, 2, 3, 1));
+    inner
+        .elements
+        .push(Arc::new(RwLock::new(text(1, 1, "nested!"))));
+    let mut outer = Container::new(Rect::new(3, 2, 5, 3));
+    outer.elements.push(Arc::new(RwLock::new(LineBox {
+        x: 1,
+        y: 1,
+        z: 0,
+        width: 9,
+        height: 3,
+        line_color: None,
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        line_set: LINES_LIGHT,
+    })));
+    outer
+        .elements
+        .push(Arc::new(RwLock::new(Container { z: 1, ..inner })));
+    let outer = Arc::new(RwLock::new(outer));
+
+    let backend = TestBackend::new(9, 5);
+    let tui = StaticTUI::with_backend(Box::new(backend.clone()));
+    tui.write().unwrap().elements.push(outer.clone());
+    tui.read().unwrap().update();
+    backend.assert_lines(&[
+        "         ",
+        "  ┌────  ",
+        "  │nes   ",
+        "  └────  ",
+        "         ",
+    ]);
+
+    outer.write().unwrap().x = 1;
+    tui.read().unwrap().update();
+    backend.assert_lines(&[
+        "         ",
+        "┌────    ",
+        "│nes     ",
+        "└────    ",
+        "         ",
+    ]);
+
+    outer.write().unwrap().visible = false;
+    tui.read().unwrap().update();
+    backend.assert_lines(&["         "; 5]);
+}
+
+#[test]
+fn mouse_events_are_translated_into_child_space() {
+    let _lock = MOUSE_EVENTS.lock().unwrap_or_else(|e| e.into_inner());
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    let child = Arc::new(RwLock::new(Interactable {
+        x: 3,
+        y: 2,
+        width: 2,
+        height: 1,
+        mouse_action: MouseAction(Arc::new(move |event: MouseEvent| {
+            recorded.lock().unwrap().push((event.x, event.y));
+        })),
+        keyboard_action: Default::default(),
+        selected: false,
+        enabled: true,
+    }));
+    let mut container = Container::new(Rect::new(5, 5, 10, 4));
+    container.reactive_elements.push(child.clone());
+    let container = Arc::new(RwLock::new(container));
+
+    let (tui, _, mouse_observer) =
+        ReactiveTUI::with_backend(0, 0, Box::new(TestBackend::new(20, 10)));
+    tui.write()
+        .unwrap()
+        .reactive_elements
+        .push(container.clone());
+    for (x, y) in [(7, 6), (8, 6), (9, 6), (5, 5)] {
+        mouse_observer.handle_mouse_event(MouseEvent { code: 0, x, y });
+    }
+    assert_eq!(*events.lock().unwrap(), vec![(0, 0), (1, 0)]);
+
+    // disabling the container disables its subtree
+    container.write().unwrap().set_enabled(false);
+    assert!(!child.read().unwrap().enabled);
+}
+
+fn key(view: &ScrollView, codes: &[u8]) {
+    for &code in codes {
+        view.keyboard(KeyEvent { code });
+    }
+}
+
+fn mouse(view: &ScrollView, code: u8, x: u8, y: u8) {
+    view.mouse(MouseEvent { code, x, y });
+}
+
+#[test]
+fn scroll_view_scrolls_with_keys_wheel_and_bar() {
+    let keys = Arc::new(Mutex::new(Vec::new()));
+    let recorded = keys.clone();
+    let mut view = ScrollView::new(Rect::new(1, 1, 6, 3));
+    for i in 1..=6 {
+        view.elements
+            .push(Arc::new(RwLock::new(text(1, i, &format!("line{}", i)))));
+    }
+    view.reactive_elements
+        .push(Arc::new(RwLock::new(Interactable {
+            x: 1,
+            y: 1,
+            width: 5,
+            height: 1,
+            mouse_action: Default::default(),
+            keyboard_action: KeyAction(Arc::new(move |event: KeyEvent| {
+                recorded.lock().unwrap().push(event.code);
+            })),
+            selected: false,
+            enabled: true,
+        })));
+    let view = Arc::new(RwLock::new(view));
+    let backend = TestBackend::new(6, 3);
+    let tui = StaticTUI::with_backend(Box::new(backend.clone()));
+    tui.write().unwrap().elements.push(view.clone());
+    let update = |expected: &[&str]| {
+        tui.read().unwrap().update();
+        backend.assert_lines(expected);
+    };
+
+    assert_eq!(view.read().unwrap().get_content_size(), (5, 6));
+    update(&["line1┃", "line2│", "line3│"]);
+    key(
+        &view.read().unwrap(),
+        &[ESCAPE, CONTROL_SEQUENCE, ARROW_DOWN],
+    );
+    update(&["line2│", "line3┃", "line4│"]);
+    key(
+        &view.read().unwrap(),
+        &[ESCAPE, CONTROL_SEQUENCE, PAGE_DOWN, SEQUENCE_END],
+    );
+    update(&["line4│", "line5│", "line6┃"]);
+    mouse(&view.read().unwrap(), MOUSE_WHEEL_UP, 2, 1);
+    update(&["line1┃", "line2│", "line3│"]);
+
+    // pressing the track jumps there, dragging moves the thumb with the mouse
+    mouse(&view.read().unwrap(), MOUSE_LEFT_PRESS, 5, 2);
+    assert_eq!(view.read().unwrap().get_scroll(), (0, 3));
+    mouse(&view.read().unwrap(), MOUSE_LEFT_DRAG, 5, 1);
+    mouse(&view.read().unwrap(), MOUSE_RELEASE, 5, 1);
+    update(&["line3│", "line4┃", "line5│"]);
+
+    // other keys, including unknown sequences, reach the child
+    key(
+        &view.read().unwrap(),
+        &[LOWERCASE_A, ESCAPE, CONTROL_SEQUENCE, 90],
+    );
+    assert_eq!(
+        *keys.lock().unwrap(),
+        vec![LOWERCASE_A, ESCAPE, CONTROL_SEQUENCE, 90]
+    );
+    assert_eq!(view.read().unwrap().get_scroll(), (0, 2));
+}
+
+#[test]
+fn scroll_view_flushes_unfinished_sequences() {
+    let _lock = MOUSE_EVENTS.lock().unwrap_or_else(|e| e.into_inner());
+    let keys = Arc::new(Mutex::new(Vec::new()));
+    let recorded = keys.clone();
+    let mut view = ScrollView::new(Rect::new(1, 1, 6, 3));
+    view.reactive_elements
+        .push(Arc::new(RwLock::new(Interactable {
+            x: 1,
+            y: 1,
+            width: 5,
+            height: 1,
+            mouse_action: Default::default(),
+            keyboard_action: KeyAction(Arc::new(move |event: KeyEvent| {
+                recorded.lock().unwrap().push(event.code);
+            })),
+            selected: false,
+            enabled: true,
+        })));
+    let view = Arc::new(RwLock::new(view));
+    let (tui, key_observer, mouse_observer) =
+        ReactiveTUI::with_backend(0, 0, Box::new(TestBackend::new(10, 3)));
+    tui.write().unwrap().elements.push(view.clone());
+    tui.write().unwrap().reactive_elements.push(view.clone());
+    let keys_pressed = |codes: &[u8]| {
+        for &code in codes {
+            key_observer.handle_key_event(KeyEvent { code });
+        }
+    };
+
+    // the start of a mouse event outside of the scroll view is dropped
+    keys_pressed(&[ESCAPE, CONTROL_SEQUENCE]);
+    mouse_observer.handle_mouse_event(MouseEvent {
+        code: MOUSE_LEFT_PRESS,
+        x: 8,
+        y: 1,
+    });
+    keys_pressed(&[LOWERCASE_A]);
+    assert_eq!(*keys.lock().unwrap(), vec![LOWERCASE_A]);
+
+    // a lone escape key reaches the child with the next key, drawing forwards nothing
+    keys_pressed(&[ESCAPE]);
+    sleep(Duration::from_millis(60));
+    tui.read().unwrap().update();
+    assert_eq!(*keys.lock().unwrap(), vec![LOWERCASE_A]);
+    keys_pressed(&[CONTROL_SEQUENCE]);
+    assert_eq!(
+        *keys.lock().unwrap(),
+        vec![LOWERCASE_A, ESCAPE, CONTROL_SEQUENCE]
+    );
+}
+
+#[test]
+fn scroll_view_shows_both_bars_when_needed() {
+    let mut view = ScrollView::new(Rect::new(1, 1, 5, 3));
+    view.elements
+        .push(Arc::new(RwLock::new(text(1, 1, "abcdefgh"))));
+    view.elements.push(Arc::new(RwLock::new(text(5, 4, "x"))));
+    let view = Arc::new(RwLock::new(view));
+    let backend = TestBackend::new(5, 3);
+    let tui = StaticTUI::with_backend(Box::new(backend.clone()));
+    tui.write().unwrap().elements.push(view.clone());
+    tui.read().unwrap().update();
+    backend.assert_lines(&["abcd┃", "    │", "━━── "]);
+
+    view.read().unwrap().scroll_to(5, 3);
+    tui.read().unwrap().update();
+    backend.assert_lines(&["    │", "x   ┃", "──━━ "]);
+
+    let text_box = TextBox {
+        x: 1,
+        y: 1,
+        z: 0,
+        width: 4,
+        height: 1,
+        text_color: None,
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        text: "aa bb cc".to_string(),
+        markup: false,
+    };
+    assert_eq!(text_box.get_line_count(), 3);
+}
@@ -0,0 +1,219 @@
+use charflow::backend::{MemoryOutput, TestBackend, WriterBackend};
+use charflow::buffer::Buffer;
+use charflow::cursor::{Cursor, CursorShape};
+use charflow::elements::complex::Container;
+use charflow::elements::simple::TextInput;
+use charflow::input::callbacks::{ARROW_LEFT, CONTROL_SEQUENCE, DELETE, ESCAPE, MOUSE_MOVE};
+use charflow::input::{KeyEvent, KeyEventObserver, MouseEvent, MouseEventObserver};
+use charflow::layout::Rect;
+use charflow::style::Style;
+use charflow::tui::{Reactive, ReactiveTUI, Renderer, TUI};
+use std::sync::{Arc, RwLock};
+
+/// Key code of the tab key, used to move the selection.
+const TAB: u8 = 9;
+
+/// Text entry stand-in showing the cursor while selected.
+struct Entry {
+    y: u16,
+    selected: bool,
+    enabled: bool,
+}
+
+impl Reactive for Entry {
+    fn keyboard(&self, _data: KeyEvent) {}
+
+    fn mouse(&self, _data: MouseEvent) {}
+
+    fn get_x(&self) -> u16 {
+        2
+    }
+
+    fn get_y(&self) -> u16 {
+        self.y
+    }
+
+    fn get_width(&self) -> u16 {
+        4
+    }
+
+    fn get_height(&self) -> u16 {
+        1
+    }
+
+    fn set_selected(&mut self, selected: bool) {
+        self.selected = selected;
+    }
+
+    fn get_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn get_cursor(&self) -> Option<Cursor> {
+        self.selected.then(|| Cursor::bar(4, self.y))
+    }
+}
+
+#[test]
+fn selected_element_places_the_cursor() {
+    let backend = TestBackend::new(6, 2);
+    let (tui, _, _) = ReactiveTUI::with_backend(0, 1, Box::new(backend.clone()));
+    let entry = Arc::new(RwLock::new(Entry {
+        y: 1,
+        selected: false,
+        enabled: true,
+    }));
+    tui.write().unwrap().reactive_elements.push(entry.clone());
+
+    tui.read().unwrap().update();
+    assert_eq!(backend.get_cursor(), None);
+
+    entry.write().unwrap().selected = true;
+    tui.read().unwrap().update();
+    assert_eq!(backend.get_cursor(), Some(Cursor::bar(4, 1)));
+
+    entry.write().unwrap().enabled = false;
+    tui.read().unwrap().update();
+    assert_eq!(backend.get_cursor(), None);
+}
+
+#[test]
+fn cursor_follows_the_selection() {
+    let backend = TestBackend::new(6, 2);
+    let (tui, key_observer, _) = ReactiveTUI::with_backend(TAB, 0, Box::new(backend.clone()));
+    let entry = |y| {
+        Arc::new(RwLock::new(Entry {
+            y,
+            selected: false,
+            enabled: true,
+        }))
+    };
+    let (first, second) = (entry(1), entry(2));
+    tui.write().unwrap().reactive_elements = vec![first.clone(), second.clone()];
+
+    key_observer.handle_key_event(KeyEvent { code: TAB });
+    tui.read().unwrap().update();
+    assert!(!first.read().unwrap().selected);
+    assert_eq!(backend.get_cursor(), Some(Cursor::bar(4, 2)));
+
+    // children of a container are selected the same way
+    let mut container = Container::new(Rect::new(1, 1, 6, 2));
+    container.reactive_elements = vec![first, second];
+    container.selected_element = 1;
+    let container = Arc::new(RwLock::new(container));
+    container.write().unwrap().set_selected(true);
+    assert_eq!(
+        container.read().unwrap().get_cursor(),
+        Some(Cursor::bar(4, 2))
+    );
+}
+
+#[test]
+fn text_input_edits_at_the_cursor() {
+    let backend = TestBackend::new(6, 1);
+    let (tui, key_observer, _) = ReactiveTUI::with_backend(TAB, 0, Box::new(backend.clone()));
+    let input = Arc::new(RwLock::new(TextInput::new(2, 1, 4)));
+    tui.write().unwrap().reactive_elements.push(input.clone());
+    tui.write().unwrap().elements.push(input.clone());
+
+    tui.read().unwrap().update();
+    assert_eq!(backend.get_cursor(), None);
+
+    let keys = |codes: &[u8]| {
+        for code in codes {
+            key_observer.handle_key_event(KeyEvent { code: *code });
+        }
+        tui.read().unwrap().update();
+    };
+    keys(b"abd");
+    keys(&[ESCAPE, CONTROL_SEQUENCE, ARROW_LEFT, b'c']);
+    assert_eq!(input.read().unwrap().get_text(), "abcd");
+    backend.assert_lines(&[" abcd "]);
+    assert_eq!(backend.get_cursor(), Some(Cursor::bar(5, 1)));
+
+    keys(&[DELETE, DELETE, DELETE, DELETE]);
+    assert_eq!(input.read().unwrap().get_text(), "d");
+    backend.assert_lines(&[" d    "]);
+    assert_eq!(backend.get_cursor(), Some(Cursor::bar(2, 1)));
+
+    // longer text scrolls to keep the cursor inside
+    input.read().unwrap().set_text("hello");
+    tui.read().unwrap().update();
+    backend.assert_lines(&[" llo  "]);
+    assert_eq!(backend.get_cursor(), Some(Cursor::bar(5, 1)));
+}
+
+#[test]
+fn text_input_ignores_the_start_of_mouse_reports() {
+    let (tui, key_observer, mouse_observer) =
+        ReactiveTUI::with_backend(TAB, 0, Box::new(TestBackend::new(6, 2)));
+    let input = Arc::new(RwLock::new(TextInput::new(1, 1, 6)));
+    tui.write().unwrap().reactive_elements.push(input.clone());
+    let keys = |codes: &[u8]| {
+        for code in codes {
+            key_observer.handle_key_event(KeyEvent { code: *code });
+        }
+    };
+
+    // reported outside of the input
+    keys(&[ESCAPE, CONTROL_SEQUENCE]);
+    mouse_observer.handle_mouse_event(MouseEvent {
+        code: MOUSE_MOVE,
+        x: 1,
+        y: 2,
+    });
+    keys(b"C");
+    assert_eq!(input.read().unwrap().get_text(), "C");
+
+    // reported above the input
+    keys(&[ESCAPE, CONTROL_SEQUENCE]);
+    input.read().unwrap().mouse(MouseEvent {
+        code: MOUSE_MOVE,
+        x: 0,
+        y: 0,
+    });
+    keys(b"D");
+    assert_eq!(input.read().unwrap().get_text(), "CD");
+}
+
+#[test]
+fn cursor_is_hidden_while_drawing() {
+    let output = MemoryOutput::new();
+    let mut renderer = Renderer::new(Box::new(WriterBackend::new(output.clone(), 4, 2)));
+    let cursor = Cursor {
+        x: 2,
+        y: 2,
+        shape: CursorShape::Underline,
+        blinking: true,
+    };
+    renderer
+        .draw_with_cursor(Buffer::new(4, 2), Some(cursor))
+        .unwrap();
+    assert!(output.get_string().ends_with("\x1b[2;2H\x1b[3 q\x1b[?25h"));
+
+    // an unchanged frame leaves the cursor alone
+    output.clear();
+    renderer
+        .draw_with_cursor(Buffer::new(4, 2), Some(cursor))
+        .unwrap();
+    assert_eq!(output.get_string(), "");
+
+    output.clear();
+    let mut buffer = Buffer::new(4, 2);
+    buffer.set(1, 1, 'a', Style::default());
+    renderer
+        .draw_with_cursor(buffer.clone(), Some(cursor))
+        .unwrap();
+    assert_eq!(
+        output.get_string(),
+        "\x1b[?25l\x1b[1;1H\x1b[0ma\x1b[0m\x1b[2;2H\x1b[3 q\x1b[?25h"
+    );
+
+    output.clear();
+    renderer.draw(buffer).unwrap();
+    assert_eq!(output.get_string(), "\x1b[?25l");
+}
@@ -0,0 +1,94 @@
+use charflow::backend::TestBackend;
+use charflow::buffer::Buffer;
+use charflow::colors::{ORANGE, RED};
+use charflow::elements::simple::Text;
+use charflow::export::{export, to_html, to_svg, to_text, ExportFormat};
+use charflow::style::{Attributes, Style};
+use charflow::theme::Theme;
+use charflow::tui::{StaticTUI, TUI};
+use std::sync::{Arc, RwLock};
+
+fn sample() -> Buffer {
+    let mut buffer = Buffer::new(6, 2);
+    buffer.set_string(1, 1, "a<b", Style::new(Some(ORANGE), None));
+    let bold = Attributes {
+        bold: true,
+        ..Default::default()
+    };
+    buffer.set_string(
+        2,
+        2,
+        "界!",
+        Style::new(None, Some(RED)).with_attributes(bold),
+    );
+    buffer
+}
+
+#[test]
+fn text_export_trims_rows() {
+    assert_eq!(to_text(&sample()), "a<b\n 界!\n");
+}
+
+#[test]
+fn ansi_export_uses_style_escapes() {
+    let ansi = export(&sample(), ExportFormat::Ansi);
+    assert_eq!(ansi.lines().count(), 2);
+    let style = Style::new(Some(ORANGE), None);
+    assert!(ansi.starts_with(&format!("{}a<b", style.to_escape())));
+    assert!(ansi.ends_with("\x1b[0m\n"));
+}
+
+#[test]
+fn html_export_inlines_styles() {
+    let html = to_html(&sample());
+    assert!(html.contains(&format!(
+        "<span style=\"color: {}; background-color: {};\">a&lt;b</span>",
+        ORANGE,
+        Theme::dark().surface
+    )));
+    assert!(html.contains(&format!(
+        "background-color: {}; font-weight: bold;\">界!",
+        RED
+    )));
+}
+
+#[test]
+fn svg_export_places_runs_on_the_grid() {
+    let svg = to_svg(&sample());
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains(&format!(
+        "<rect x=\"10\" y=\"20\" width=\"30\" height=\"20\" fill=\"{}\"/>",
+        RED
+    )));
+    assert!(svg.contains(">a&lt;b</text>"));
+    assert!(svg.contains("textLength=\"30\""));
+}
+
+#[test]
+fn screenshots_match_the_rendered_frame() {
+    let tui = StaticTUI::with_backend(Box::new(TestBackend::new(8, 1)));
+    tui.write()
+        .unwrap()
+        .elements
+        .push(Arc::new(RwLock::new(Text {
+            x: 2,
+            y: 1,
+            z: 0,
+            text: "hi".to_string(),
+            text_color: None,
+            bg_color: None,
+            attributes: Attributes::default(),
+            visible: true,
+            markup: false,
+        })));
+    let screenshot = tui.read().unwrap().screenshot();
+    assert_eq!(screenshot.get_lines(), vec![" hi     "]);
+
+    let path = std::env::temp_dir().join("charflow_screenshot.txt");
+    tui.read()
+        .unwrap()
+        .save_screenshot(&path, ExportFormat::Text)
+        .unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), " hi\n");
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,378 @@
+use charflow::backend::TestBackend;
+use charflow::elements::complex::{Anchored, FlexBox, GridBox};
+use charflow::elements::simple::line_box::LINES_LIGHT;
+use charflow::elements::simple::{Box as LineBox, Text};
+use charflow::input::observers::TuiResizeObserver;
+use charflow::input::{ResizeEvent, ResizeEventObserver};
+use charflow::layout::{
+    Alignment, Anchor, Constraint, Extent, Flex, Grid, GridPlacement, Offset, Padding, Rect, Track,
+};
+use charflow::style::Attributes;
+use charflow::tui::{Element, StaticTUI, TUI};
+use std::sync::{Arc, RwLock};
+
+fn widths(flex: Flex, width: u16, constraints: &[Constraint]) -> Vec<(u16, u16)> {
+    flex.split(Rect::new(1, 1, width, 1), constraints)
+        .iter()
+        .map(|rect| (rect.x, rect.width))
+        .collect()
+}
+
+#[test]
+fn constraints_share_the_space() {
+    use Constraint::*;
+    let row = Flex::row();
+    assert_eq!(
+        widths(row, 20, &[Length(4), Percentage(50), Fill(1)]),
+        vec![(1, 4), (5, 10), (15, 6)]
+    );
+    assert_eq!(
+        widths(row, 10, &[Fill(1), Fill(2), Length(1)]),
+        vec![(1, 3), (4, 6), (10, 1)]
+    );
+    assert_eq!(
+        widths(row, 10, &[Min(2), Max(3), Min(1)]),
+        vec![(1, 4), (5, 3), (8, 3)]
+    );
+    assert_eq!(
+        widths(row, 9, &[Ratio(1, 3), Ratio(2, 3)]),
+        vec![(1, 3), (4, 6)]
+    );
+    // too little space, max shrinks before length and min
+    assert_eq!(
+        widths(row, 6, &[Min(3), Length(3), Max(3)]),
+        vec![(1, 3), (4, 3), (7, 0)]
+    );
+    assert_eq!(widths(row, 4, &[Min(3), Length(3)]), vec![(1, 3), (4, 1)]);
+}
+
+#[test]
+fn gaps_padding_and_alignment_place_children() {
+    use Constraint::Length;
+    let flex = Flex {
+        gap: 1,
+        padding: Padding::symmetric(1, 2),
+        ..Flex::row()
+    };
+    let rects = flex.split(Rect::new(1, 1, 12, 4), &[Length(2), Length(2)]);
+    assert_eq!(rects, vec![Rect::new(3, 2, 2, 2), Rect::new(6, 2, 2, 2)]);
+
+    let aligned = |alignment| {
+        widths(
+            Flex {
+                alignment,
+                ..Flex::row()
+            },
+            10,
+            &[Length(2), Length(2)],
+        )
+    };
+    assert_eq!(aligned(Alignment::Center), vec![(4, 2), (6, 2)]);
+    assert_eq!(aligned(Alignment::End), vec![(7, 2), (9, 2)]);
+    assert_eq!(aligned(Alignment::SpaceBetween), vec![(1, 2), (9, 2)]);
+    assert_eq!(aligned(Alignment::SpaceEvenly), vec![(3, 2), (7, 2)]);
+}
+
+fn line_box() -> LineBox {
+    LineBox {
+        x: 0,
+        y: 0,
+        z: 0,
+        width: 0,
+        height: 0,
+        line_color: None,
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        line_set: LINES_LIGHT,
+    }
+}
+
+#[test]
+fn flex_box_follows_the_screen_size() {
+    let mut columns = FlexBox::new(Flex::row());
+    columns.push(Constraint::Length(3), Arc::new(RwLock::new(line_box())));
+    columns.push(Constraint::Fill(1), Arc::new(RwLock::new(line_box())));
+    let mut root = FlexBox::new(Flex::column());
+    root.push(Constraint::Fill(1), Arc::new(RwLock::new(columns)));
+    root.push(
+        Constraint::Length(1),
+        Arc::new(RwLock::new(Text {
+            x: 0,
+            y: 0,
+            z: 0,
+            text_color: None,
+            bg_color: None,
+            attributes: Attributes::default(),
+            visible: true,
+            text: "status".to_string(),
+            markup: false,
+        })),
+    );
+    let root = Arc::new(RwLock::new(root));
+
+    for (width, height, expected) in [
+        (8, 4, vec!["┌─┐┌───┐", "│ ││   │", "└─┘└───┘", "status  "]),
+        (10, 3, vec!["┌─┐┌─────┐", "└─┘└─────┘", "status    "]),
+    ] {
+        let backend = TestBackend::new(width, height);
+        let tui = StaticTUI::with_backend(Box::new(backend.clone()));
+        tui.write().unwrap().elements.push(root.clone());
+        tui.read().unwrap().update();
+        backend.assert_lines(&expected);
+    }
+}
+
+#[test]
+fn grid_places_spans_and_named_areas() {
+    let grid = Grid {
+        column_gap: 1,
+        areas: vec!["side main main".to_string(), "side . foot".to_string()],
+        ..Grid::new(
+            vec![Track::Auto, Track::Fraction(1), Track::Length(3)],
+            vec![Track::Fraction(2), Track::Fraction(1)],
+        )
+    };
+    let placements = [
+        GridPlacement::cell(0, 0),
+        GridPlacement::span(1, 1, 2, 1),
+        GridPlacement::area("main"),
+        GridPlacement::area("side"),
+        GridPlacement::area("nothing"),
+        GridPlacement::cell(3, 0),
+    ];
+    let rects = grid.place(
+        Rect::new(1, 1, 20, 6),
+        &placements,
+        &[(5, 1), (0, 0), (0, 0), (0, 0), (0, 0), (0, 0)],
+    );
+    assert_eq!(
+        rects,
+        vec![
+            Some(Rect::new(1, 1, 5, 4)),
+            Some(Rect::new(7, 5, 14, 2)),
+            Some(Rect::new(7, 1, 14, 4)),
+            Some(Rect::new(1, 1, 5, 6)),
+            None,
+            None,
+        ]
+    );
+}
+
+#[test]
+fn grid_box_assigns_cells_to_elements() {
+    let mut grid = GridBox::new(Grid::new(
+        vec![Track::Auto, Track::Fraction(1)],
+        vec![Track::Length(3), Track::Fraction(1)],
+    ));
+    let label = Text {
+        x: 0,
+        y: 0,
+        z: 0,
+        text_color: None,
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        text: "cpu".to_string(),
+        markup: false,
+    };
+    grid.push(GridPlacement::cell(0, 0), Arc::new(RwLock::new(label)));
+    grid.push(GridPlacement::cell(1, 0), Arc::new(RwLock::new(line_box())));
+    grid.push(
+        GridPlacement::span(0, 1, 2, 1),
+        Arc::new(RwLock::new(line_box())),
+    );
+
+    let backend = TestBackend::new(9, 5);
+    let tui = StaticTUI::with_backend(Box::new(backend.clone()));
+    tui.write()
+        .unwrap()
+        .elements
+        .push(Arc::new(RwLock::new(grid)));
+    tui.read().unwrap().update();
+    backend.assert_lines(&[
+        "cpu┌────┐",
+        "   │    │",
+        "   └────┘",
+        "┌───────┐",
+        "└───────┘",
+    ]);
+}
+
+#[test]
+fn resizing_redraws_the_layout_right_away() {
+    let mut root = FlexBox::new(Flex::column());
+    root.push(Constraint::Fill(1), Arc::new(RwLock::new(line_box())));
+    let backend = TestBackend::new(4, 2);
+    let tui = StaticTUI::with_backend(Box::new(backend.clone()));
+    tui.write()
+        .unwrap()
+        .elements
+        .push(Arc::new(RwLock::new(root)));
+    tui.read().unwrap().update();
+    backend.assert_lines(&["┌──┐", "└──┘"]);
+
+    backend.resize(6, 3);
+    let observer = TuiResizeObserver { tui: tui.clone() };
+    observer.handle_resize_event(ResizeEvent {
+        width: 6,
+        height: 3,
+    });
+    assert_eq!(tui.read().unwrap().get_size(), (6, 3));
+    backend.assert_lines(&["┌────┐", "│    │", "└────┘"]);
+}
+
+#[test]
+fn anchors_resolve_against_the_reference() {
+    let screen = Rect::new(1, 1, 20, 10);
+    let current = Rect::new(0, 0, 4, 2);
+    assert_eq!(
+        Anchor::bottom_bar(1).resolve(screen, current),
+        Rect::new(1, 10, 20, 1)
+    );
+    assert_eq!(
+        Anchor::centered(60, 40).resolve(screen, current),
+        Rect::new(5, 4, 12, 4)
+    );
+    let text_box = Rect::new(3, 2, 5, 3);
+    assert_eq!(
+        Anchor::right_of(2).resolve(text_box, current),
+        Rect::new(10, 2, 4, 2)
+    );
+    let left_of = Anchor {
+        x: Offset::Before(1),
+        ..Anchor::right_of(0)
+    };
+    // 3 of the 4 columns are before the screen
+    assert_eq!(left_of.resolve(text_box, current), Rect::new(1, 2, 1, 2));
+    let inset = Anchor {
+        x: Offset::End(2),
+        y: Offset::Percent(50),
+        width: Extent::Fill,
+        height: Extent::Keep,
+    };
+    assert_eq!(inset.resolve(screen, current), Rect::new(1, 6, 18, 2));
+}
+
+#[test]
+fn anchors_before_the_screen_are_cut() {
+    let reference = Rect::new(1, 2, 5, 1);
+    let current = Rect::new(0, 0, 4, 3);
+    let above = Anchor {
+        x: Offset::Start(0),
+        y: Offset::Before(1),
+        width: Extent::Keep,
+        height: Extent::Keep,
+    };
+    // starts at -2, the first 3 lines are cut
+    assert_eq!(above.resolve(reference, current), Rect::new(1, 1, 4, 0));
+    let overlapping = Anchor {
+        x: Offset::Before(0),
+        y: Offset::Start(0),
+        ..above
+    };
+    assert_eq!(
+        overlapping.resolve(reference, current),
+        Rect::new(1, 2, 0, 3)
+    );
+    let partly = Anchor {
+        y: Offset::Center(0),
+        ..above
+    };
+    assert_eq!(
+        partly.resolve(Rect::new(1, 1, 5, 1), current),
+        Rect::new(1, 1, 4, 2)
+    );
+}
+
+#[test]
+fn anchored_elements_follow_the_screen_and_references() {
+    let reference = Arc::new(RwLock::new(LineBox {
+        x: 2,
+        y: 1,
+        width: 3,
+        height: 2,
+        ..line_box()
+    }));
+    let status = Arc::new(RwLock::new(Text {
+        x: 0,
+        y: 0,
+        z: 0,
+        text_color: None,
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        text: "ok".to_string(),
+        markup: false,
+    }));
+    let backend = TestBackend::new(8, 4);
+    let tui = StaticTUI::with_backend(Box::new(backend.clone()));
+    tui.write().unwrap().elements = vec![
+        reference.clone(),
+        Arc::new(RwLock::new(Anchored::new(
+            Anchor {
+                x: Offset::End(0),
+                width: Extent::Keep,
+                ..Anchor::bottom_bar(1)
+            },
+            status.clone(),
+        ))),
+        Arc::new(RwLock::new(Anchored::relative_to(
+            Anchor::right_of(1),
+            reference,
+            Arc::new(RwLock::new(LineBox {
+                width: 2,
+                height: 2,
+                ..line_box()
+            })),
+        ))),
+    ];
+    tui.read().unwrap().update();
+    backend.assert_lines(&[" ┌─┐ ┌┐ ", " └─┘ └┘ ", "        ", "      ok"]);
+}
+
+#[test]
+fn anchored_elements_keep_their_size_when_cut() {
+    let reference = Arc::new(RwLock::new(LineBox {
+        x: 3,
+        y: 1,
+        width: 2,
+        height: 2,
+        ..line_box()
+    }));
+    let placed = Arc::new(RwLock::new(LineBox {
+        width: 4,
+        height: 2,
+        ..line_box()
+    }));
+    let backend = TestBackend::new(8, 2);
+    let tui = StaticTUI::with_backend(Box::new(backend.clone()));
+    tui.write().unwrap().elements = vec![
+        reference.clone(),
+        Arc::new(RwLock::new(Anchored::relative_to(
+            Anchor {
+                x: Offset::Before(1),
+                ..Anchor::right_of(0)
+            },
+            reference.clone(),
+            placed.clone(),
+        ))),
+    ];
+    // starts at -2, only the right edge is visible
+    for _ in 0..3 {
+        tui.read().unwrap().update();
+        backend.assert_lines(&["┐ ┌┐    ", "┘ └┘    "]);
+        assert_eq!(
+            placed.read().unwrap().get_rect(),
+            Some(Rect::new(1, 1, 1, 2))
+        );
+    }
+
+    reference.write().unwrap().x = 7;
+    tui.read().unwrap().update();
+    backend.assert_lines(&[" ┌──┐ ┌┐", " └──┘ └┘"]);
+    assert_eq!(
+        placed.read().unwrap().get_rect(),
+        Some(Rect::new(2, 1, 4, 2))
+    );
+}
@@ -0,0 +1,78 @@
+use charflow::colors::ORANGE;
+use charflow::elements::wrap_lines;
+use charflow::rich_text::{parse_markup, Line, Span};
+use charflow::style::{Attributes, Style, Underline};
+use charflow::Color;
+
+fn bold(fg_color: Option<Color>) -> Style {
+    Style::new(fg_color, None).with_attributes(Attributes {
+        bold: true,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn tags_style_until_closed() {
+    assert_eq!(
+        parse_markup("[bold red]error[/]: file not found"),
+        vec![Line {
+            spans: vec![
+                Span::new("error", bold(Some(Color::new(0xFF, 0, 0)))),
+                Span::new(": file not found", Style::default()),
+            ]
+        }]
+    );
+}
+
+#[test]
+fn nested_tags_stack() {
+    let lines = parse_markup("[yellow on grey]a[underline]b[/]c[/]d\ne");
+    let colored = Style::new(
+        Some(Color::new(0xFF, 0xFF, 0)),
+        Some(Color::new(0x80, 0x80, 0x80)),
+    );
+    let underlined = colored.with_attributes(Attributes {
+        underline: Underline::Single,
+        ..Default::default()
+    });
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[0].spans,
+        vec![
+            Span::new("a", colored),
+            Span::new("b", underlined),
+            Span::new("c", colored),
+            Span::new("d", Style::default()),
+        ]
+    );
+    assert_eq!(lines[1].get_plain(), "e");
+}
+
+#[test]
+fn invalid_tags_stay_text() {
+    let lines = parse_markup("[[x] [not a tag] [#ffb000]hex[/] [/] [");
+    assert_eq!(lines[0].get_plain(), "[x] [not a tag] hex  [");
+    assert_eq!(lines[0].spans[1].style.fg_color, Some(ORANGE));
+}
+
+#[test]
+fn wrapping_keeps_span_styles() {
+    let lines = wrap_lines(&parse_markup("one [bold]two three[/] four"), 11);
+    assert_eq!(
+        lines,
+        vec![
+            Line {
+                spans: vec![
+                    Span::new("one ", Style::default()),
+                    Span::new("two", bold(None)),
+                ]
+            },
+            Line {
+                spans: vec![
+                    Span::new("three", bold(None)),
+                    Span::new(" four", Style::default()),
+                ]
+            },
+        ]
+    );
+}
@@ -0,0 +1,67 @@
+use charflow::backend::{Backend, MemoryOutput, WriterBackend};
+use charflow::buffer::Buffer;
+use charflow::recorder::Recorder;
+use charflow::style::Style;
+use charflow::tui::Renderer;
+
+#[test]
+fn frames_are_recorded_as_output_events() {
+    let cast = MemoryOutput::new();
+    let recorder = Recorder::new(cast.clone(), 4, 1).unwrap();
+    let mut backend = WriterBackend::new(MemoryOutput::new(), 4, 1);
+    backend.set_recorder(Some(recorder.clone()));
+
+    let mut buffer = Buffer::new(4, 1);
+    buffer.set_string(1, 1, "\"hi\"", Style::default());
+    backend.draw(&buffer.content()).unwrap();
+    backend.flush().unwrap();
+    recorder.stop().unwrap();
+    assert!(!recorder.is_recording());
+
+    backend.draw(&buffer.content()).unwrap();
+    backend.flush().unwrap();
+
+    let content = cast.get_string();
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("{\"version\": 2, \"width\": 4, \"height\": 1, \"timestamp\": "));
+    assert!(lines[1].starts_with('['));
+    assert!(lines[1].ends_with(", \"o\", \"\\u001b[1;1H\\u001b[0m\\\"hi\\\"\\u001b[0m\"]"));
+}
+
+#[test]
+fn split_characters_are_recorded_once_complete() {
+    let cast = MemoryOutput::new();
+    let recorder = Recorder::new(cast.clone(), 1, 1).unwrap();
+    let bytes = "é".as_bytes();
+    recorder.record_input(&bytes[..1]).unwrap();
+    recorder.record_input(&bytes[1..]).unwrap();
+    recorder.record_input(b"\n").unwrap();
+    let content = cast.get_string();
+    let events: Vec<&str> = content.lines().skip(1).collect();
+    assert_eq!(events.len(), 2);
+    assert!(events[0].ends_with(", \"i\", \"é\"]"));
+    assert!(events[1].ends_with(", \"i\", \"\\n\"]"));
+}
+
+#[test]
+fn recording_redraws_only_its_backend() {
+    let mut buffer = Buffer::new(2, 1);
+    buffer.set_string(1, 1, "ab", Style::default());
+    let output = MemoryOutput::new();
+    let mut other = Renderer::new(Box::new(WriterBackend::new(output.clone(), 2, 1)));
+    other.draw(buffer.clone()).unwrap();
+
+    let cast = MemoryOutput::new();
+    let recording = MemoryOutput::new();
+    let mut backend = WriterBackend::new(recording.clone(), 2, 1);
+    backend.set_recorder(Some(Recorder::new(cast.clone(), 2, 1).unwrap()));
+    let mut renderer = Renderer::new(Box::new(backend));
+    renderer.draw(buffer.clone()).unwrap();
+    assert!(recording.get_string().contains("ab"));
+
+    // unchanged frames draw nothing, the recorder was set on another backend
+    output.clear();
+    other.draw(buffer).unwrap();
+    assert_eq!(output.get_string(), "");
+}
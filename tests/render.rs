@@ -0,0 +1,546 @@
+use charflow::backend::{
+    Backend, MemoryOutput, TestBackend, WriterBackend, BEGIN_SYNCHRONIZED_UPDATE,
+    END_SYNCHRONIZED_UPDATE,
+};
+use charflow::buffer::Buffer;
+use charflow::colors::{GREY, ORANGE, ORANGE_50, YELLOW};
+use charflow::elements::complex::canvas;
+use charflow::elements::complex::element_tree::{
+    Element, Folder, Icon, Part, CLOSED_FOLDER, OPEN_FOLDER, SIMPLE_SET,
+};
+use charflow::elements::complex::{Canvas, Container, ElementTree};
+use charflow::elements::simple::line_box::LINES_LIGHT;
+use charflow::elements::simple::{Box as LineBox, Button, Fill, Text, TextBox};
+use charflow::input::{Action, KeyAction, MouseAction};
+use charflow::layout::Rect;
+use charflow::style::{Attributes, Style};
+use charflow::terminal::Viewport;
+use charflow::tui::{Element as TuiElement, RwLockElement, StaticTUI, TUI};
+use charflow::Color;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Renders the elements on a headless screen of the given size.
+fn render(width: u16, height: u16, elements: Vec<RwLockElement>) -> TestBackend {
+    let backend = TestBackend::new(width, height);
+    let tui = StaticTUI::with_backend(Box::new(backend.clone()));
+    tui.write().unwrap().elements = elements;
+    tui.read().unwrap().update();
+    backend
+}
+
+fn text(x: u16, y: u16, z: u16, text: &str) -> Text {
+    Text {
+        x,
+        y,
+        z,
+        text_color: None,
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        text: text.to_string(),
+        markup: false,
+    }
+}
+
+fn folder(name: &str, is_open: bool, children: Vec<Part>) -> Folder {
+    Folder {
+        name: name.to_string(),
+        icon_closed_id: "closed".to_string(),
+        icon_open_id: "open".to_string(),
+        is_open,
+        children,
+    }
+}
+
+fn file(name: &str) -> Part {
+    Part::Element(Element {
+        name: name.to_string(),
+        icon_id: "file".to_string(),
+    })
+}
+
+fn element_tree(width: u16, height: u16) -> ElementTree {
+    let mut icon_map = HashMap::new();
+    icon_map.insert("closed".to_string(), CLOSED_FOLDER);
+    icon_map.insert("open".to_string(), OPEN_FOLDER);
+    icon_map.insert(
+        "file".to_string(),
+        Icon {
+            color: Some(YELLOW),
+            char: '#',
+        },
+    );
+    ElementTree {
+        x: 1,
+        y: 1,
+        z: 0,
+        width,
+        height,
+        icon_map,
+        line_set: SIMPLE_SET,
+        elements: folder(
+            "main",
+            true,
+            vec![
+                Part::Folder(folder("images", false, vec![file("cat.png")])),
+                file("notes.txt"),
+                Part::Folder(folder("src", true, vec![file("lib.rs"), file("tui.rs")])),
+                file("README.md"),
+            ],
+        ),
+        element_color: Some(ORANGE),
+        line_color: None,
+        bg_color: Some(GREY),
+        attributes: Attributes::default(),
+        visible: true,
+    }
+}
+
+#[test]
+fn text_splits_lines_and_stays_on_screen() {
+    let backend = render(
+        8,
+        3,
+        vec![Arc::new(RwLock::new(text(5, 2, 0, "hello\nworld")))],
+    );
+    backend.assert_lines(&["        ", "    hell", "    worl"]);
+}
+
+#[test]
+fn elements_are_drawn_in_z_order() {
+    let backend = render(
+        5,
+        1,
+        vec![
+            Arc::new(RwLock::new(text(2, 1, 1, "top"))),
+            Arc::new(RwLock::new(text(1, 1, 0, "under"))),
+        ],
+    );
+    backend.assert_lines(&["utopr"]);
+}
+
+#[test]
+fn invisible_elements_are_skipped() {
+    let mut hidden = text(1, 1, 0, "hidden");
+    hidden.visible = false;
+    let backend = render(6, 1, vec![Arc::new(RwLock::new(hidden))]);
+    backend.assert_lines(&["      "]);
+}
+
+#[test]
+fn text_box_wraps_words() {
+    let text_box = TextBox {
+        x: 2,
+        y: 2,
+        z: 0,
+        width: 12,
+        height: 5,
+        text_color: Some(ORANGE),
+        bg_color: Some(GREY),
+        attributes: Attributes::default(),
+        visible: true,
+        text: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.".to_string(),
+        markup: false,
+    };
+    let backend = render(14, 7, vec![Arc::new(RwLock::new(text_box))]);
+    backend.assert_snapshot("tests/snapshots/text_box.txt");
+    let screen = backend.get_buffer();
+    let cell = screen.get(13, 6).unwrap();
+    assert_eq!(cell.style.bg_color, Some(GREY));
+    assert_eq!(cell.style.fg_color, Some(ORANGE));
+    assert_eq!(screen.get(14, 6).unwrap().style.bg_color, None);
+}
+
+#[test]
+#[should_panic(expected = "missing snapshot")]
+fn missing_snapshots_fail() {
+    TestBackend::new(2, 1).assert_snapshot("tests/snapshots/missing.txt");
+}
+
+#[test]
+fn line_box_outlines_area() {
+    let line_box = LineBox {
+        x: 1,
+        y: 1,
+        z: 0,
+        width: 5,
+        height: 3,
+        line_color: Some(YELLOW),
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        line_set: LINES_LIGHT,
+    };
+    let backend = render(6, 4, vec![Arc::new(RwLock::new(line_box))]);
+    backend.assert_lines(&["┌───┐ ", "│   │ ", "└───┘ ", "      "]);
+    assert_eq!(
+        backend.get_buffer().get(1, 1).unwrap().style.fg_color,
+        Some(YELLOW)
+    );
+}
+
+#[test]
+fn line_box_is_cut_at_the_screen_edge() {
+    let line_box = LineBox {
+        x: 3,
+        y: 1,
+        z: 0,
+        width: 5,
+        height: 2,
+        line_color: None,
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        line_set: LINES_LIGHT,
+    };
+    let backend = render(5, 3, vec![Arc::new(RwLock::new(line_box))]);
+    backend.assert_lines(&["  ┌──", "  └──", "     "]);
+}
+
+#[test]
+fn button_uses_selected_colors() {
+    let mut button = Button {
+        x: 1,
+        y: 1,
+        z: 0,
+        width: 6,
+        height: 2,
+        text_color: None,
+        bg_color: Some(GREY),
+        selected_text_color: Some(YELLOW),
+        selected_bg_color: Some(ORANGE_50),
+        attributes: Attributes::default(),
+        selected_attributes: Attributes::default(),
+        action: Action::default(),
+        selected: false,
+        enabled: true,
+        visible: true,
+        text: "press me".to_string(),
+        markup: false,
+    };
+    let backend = render(6, 2, vec![Arc::new(RwLock::new(button.clone()))]);
+    backend.assert_lines(&["press ", "me    "]);
+    assert_eq!(
+        backend.get_buffer().get(6, 2).unwrap().style.bg_color,
+        Some(GREY)
+    );
+
+    button.selected = true;
+    let backend = render(6, 2, vec![Arc::new(RwLock::new(button))]);
+    let screen = backend.get_buffer();
+    assert_eq!(screen.get(1, 1).unwrap().style.fg_color, Some(YELLOW));
+    assert_eq!(screen.get(6, 2).unwrap().style.bg_color, Some(ORANGE_50));
+}
+
+#[test]
+fn canvas_elements_inherit_background() {
+    let canvas = Canvas {
+        x: 2,
+        y: 1,
+        z: 0,
+        width: 4,
+        height: 2,
+        elements: vec![
+            Arc::new(RwLock::new(canvas::Element {
+                x: -1,
+                y: 0,
+                z: 0,
+                look: "####\n####".to_string(),
+                fg_color: None,
+                bg_color: Some(ORANGE_50),
+                attributes: Attributes::default(),
+            })),
+            Arc::new(RwLock::new(canvas::Element {
+                x: 1,
+                y: 1,
+                z: 1,
+                look: "@@@@".to_string(),
+                fg_color: Some(YELLOW),
+                bg_color: None,
+                attributes: Attributes::default(),
+            })),
+        ],
+        element_color: Some(ORANGE),
+        bg_color: Some(GREY),
+        mouse_action: MouseAction::default(),
+        keyboard_action: KeyAction::default(),
+        visible: true,
+        selected: false,
+        enabled: true,
+    };
+    let backend = render(6, 2, vec![Arc::new(RwLock::new(canvas))]);
+    backend.assert_lines(&[" ###  ", " #@@@ "]);
+    let screen = backend.get_buffer();
+    // element with background over the canvas background
+    assert_eq!(screen.get(2, 1).unwrap().style.bg_color, Some(ORANGE_50));
+    assert_eq!(screen.get(2, 1).unwrap().style.fg_color, Some(ORANGE));
+    // element without background keeps the one underneath
+    assert_eq!(screen.get(4, 2).unwrap().style.bg_color, Some(ORANGE_50));
+    assert_eq!(screen.get(5, 2).unwrap().style.bg_color, Some(GREY));
+    assert_eq!(screen.get(5, 2).unwrap().style.fg_color, Some(YELLOW));
+}
+
+#[test]
+fn element_tree_renders_nested_folders() {
+    let backend = render(16, 8, vec![Arc::new(RwLock::new(element_tree(16, 8)))]);
+    backend.assert_snapshot("tests/snapshots/element_tree.txt");
+    let screen = backend.get_buffer();
+    assert_eq!(screen.get(2, 2).unwrap().style.fg_color, Some(YELLOW));
+    assert_eq!(screen.get(4, 2).unwrap().style.fg_color, Some(ORANGE));
+    assert_eq!(screen.get(16, 8).unwrap().style.bg_color, Some(GREY));
+}
+
+#[test]
+fn element_tree_truncates_and_continues() {
+    let backend = render(10, 4, vec![Arc::new(RwLock::new(element_tree(10, 4)))]);
+    backend.assert_lines(&["┢>╸images ", "┣#╸notes.┅", "┣v╸src    ", "┇┢        "]);
+}
+
+#[test]
+fn element_tree_without_overflow_has_no_continuation() {
+    let backend = render(12, 6, vec![Arc::new(RwLock::new(element_tree(12, 6)))]);
+    assert_eq!(backend.get_lines()[5], "┗#╸README.md");
+}
+
+#[test]
+fn only_changed_cells_are_written() {
+    let output = MemoryOutput::new();
+    let tui = StaticTUI::with_backend(Box::new(WriterBackend::new(output.clone(), 10, 2)));
+    let element = Arc::new(RwLock::new(text(1, 1, 0, "abc")));
+    tui.write().unwrap().elements.push(element.clone());
+
+    tui.read().unwrap().update();
+    assert!(output.get_string().contains("abc"));
+
+    output.clear();
+    tui.read().unwrap().update();
+    assert_eq!(output.get_string(), "");
+
+    element.write().unwrap().text = "abd".to_string();
+    tui.read().unwrap().update();
+    assert_eq!(output.get_string(), "\x1b[1;3H\x1b[0md\x1b[0m");
+}
+
+#[test]
+fn frames_are_written_at_once_in_synchronized_updates() {
+    let output = MemoryOutput::new();
+    let mut backend = WriterBackend::new(output.clone(), 4, 1);
+    backend.set_synchronized_output(true);
+    let mut buffer = Buffer::new(4, 1);
+    buffer.set_string(1, 1, "ab", Style::default());
+    backend.draw(&buffer.content()).unwrap();
+    assert_eq!(output.get_string(), "");
+
+    backend.flush().unwrap();
+    let written = output.get_string();
+    assert!(written.starts_with(BEGIN_SYNCHRONIZED_UPDATE));
+    assert!(written.ends_with(END_SYNCHRONIZED_UPDATE));
+    assert!(written.contains("ab"));
+
+    // nothing drawn, nothing written
+    output.clear();
+    backend.flush().unwrap();
+    assert_eq!(output.get_string(), "");
+}
+
+#[test]
+fn inline_viewport_moves_relative_to_its_origin() {
+    let output = MemoryOutput::new();
+    let mut backend = WriterBackend::new(output.clone(), 10, 40);
+    backend.set_viewport(Viewport::Inline(2));
+    let tui = StaticTUI::with_backend(Box::new(backend));
+    tui.write()
+        .unwrap()
+        .elements
+        .push(Arc::new(RwLock::new(text(4, 2, 0, "ok"))));
+    tui.read().unwrap().update();
+    assert!(output.get_string().starts_with("\x1b8\x1b[1G\x1b[0m "));
+    assert!(output
+        .get_string()
+        .contains("\x1b8\x1b[1B\x1b[1G\x1b[0m   ok"));
+}
+
+#[test]
+fn wide_text_is_wrapped_and_placed_by_width() {
+    let text_box = TextBox {
+        x: 1,
+        y: 1,
+        z: 0,
+        width: 6,
+        height: 2,
+        text_color: None,
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        text: "日本 語".to_string(),
+        markup: false,
+    };
+    let canvas = Canvas {
+        x: 1,
+        y: 3,
+        z: 0,
+        width: 5,
+        height: 1,
+        elements: vec![Arc::new(RwLock::new(canvas::Element {
+            x: 0,
+            y: 0,
+            z: 0,
+            look: "a日本語".to_string(),
+            fg_color: None,
+            bg_color: None,
+            attributes: Attributes::default(),
+        }))],
+        element_color: None,
+        bg_color: None,
+        mouse_action: MouseAction::default(),
+        keyboard_action: KeyAction::default(),
+        visible: true,
+        selected: false,
+        enabled: true,
+    };
+    let backend = render(
+        6,
+        3,
+        vec![
+            Arc::new(RwLock::new(text_box)),
+            Arc::new(RwLock::new(canvas)),
+        ],
+    );
+    backend.assert_lines(&["日本  ", "語    ", "a日本 "]);
+}
+
+#[test]
+fn markup_styles_only_tagged_text() {
+    let mut error = text(1, 1, 0, "[bold red]error[/]: file not found");
+    error.text_color = Some(GREY);
+    error.markup = true;
+    let backend = render(22, 1, vec![Arc::new(RwLock::new(error))]);
+    backend.assert_lines(&["error: file not found "]);
+    let screen = backend.get_buffer();
+    assert_eq!(
+        screen.get(5, 1).unwrap().style.fg_color,
+        Some(Color::new(0xFF, 0, 0))
+    );
+    assert!(screen.get(5, 1).unwrap().style.attributes.bold);
+    assert_eq!(screen.get(6, 1).unwrap().style.fg_color, Some(GREY));
+    assert!(!screen.get(6, 1).unwrap().style.attributes.bold);
+}
+
+#[test]
+fn translucent_layers_blend_with_lower_ones() {
+    let white = Color::new(255, 255, 255);
+    let mut backdrop = text(1, 1, 0, "abcd");
+    backdrop.text_color = Some(white);
+    backdrop.bg_color = Some(Color::new(0, 0, 200));
+    let mut overlay = text(3, 1, 1, "XY");
+    overlay.text_color = Some(white.with_alpha(128));
+    overlay.bg_color = Some(Color::rgba(200, 0, 0, 128));
+    let dim = Fill {
+        x: 1,
+        y: 1,
+        z: 2,
+        width: 1,
+        height: 1,
+        color: Color::rgba(0, 0, 0, 128),
+        visible: true,
+    };
+    let backend = render(
+        4,
+        1,
+        vec![
+            Arc::new(RwLock::new(backdrop)),
+            Arc::new(RwLock::new(overlay)),
+            Arc::new(RwLock::new(dim)),
+        ],
+    );
+    backend.assert_lines(&["abXY"]);
+    let screen = backend.get_buffer();
+    // the fill keeps the content and darkens both colors
+    assert_eq!(
+        screen.get(1, 1).unwrap().style.bg_color,
+        Some(Color::new(0, 0, 100))
+    );
+    assert_eq!(
+        screen.get(1, 1).unwrap().style.fg_color,
+        Some(Color::new(127, 127, 127))
+    );
+    assert_eq!(
+        screen.get(2, 1).unwrap().style.bg_color,
+        Some(Color::new(0, 0, 200))
+    );
+    let blended = Color::new(100, 0, 100);
+    assert_eq!(screen.get(3, 1).unwrap().style.bg_color, Some(blended));
+    assert_eq!(
+        screen.get(3, 1).unwrap().style.fg_color,
+        Some(white.with_alpha(128).over(blended))
+    );
+}
+
+#[test]
+fn unset_backgrounds_keep_the_layer_below() {
+    let blue = Color::new(0, 0, 200);
+    let mut backdrop = text(1, 1, 0, "abcd");
+    backdrop.bg_color = Some(blue);
+    let overlay = text(2, 1, 1, "XY");
+    let backend = render(
+        4,
+        1,
+        vec![
+            Arc::new(RwLock::new(backdrop)),
+            Arc::new(RwLock::new(overlay)),
+        ],
+    );
+    backend.assert_lines(&["aXYd"]);
+    let screen = backend.get_buffer();
+    assert_eq!(screen.get(2, 1).unwrap().style.bg_color, Some(blue));
+    assert_eq!(screen.get(2, 1).unwrap().style.fg_color, None);
+    assert_eq!(
+        Style::default().over(Style::new(None, Some(blue))).bg_color,
+        Some(blue)
+    );
+}
+
+/// Element that ignores its bounds and writes a whole row.
+struct Overflowing(Rect);
+
+impl TuiElement for Overflowing {
+    fn print(&self, buffer: &mut Buffer) {
+        buffer.set_string(1, self.0.y, "xxxxxxxx", Style::default());
+        buffer.set_string(self.0.x, self.0.y + 1, "yyyyyyyy", Style::default());
+    }
+
+    fn get_z(&self) -> u16 {
+        0
+    }
+
+    fn get_visible(&self) -> bool {
+        true
+    }
+
+    fn set_visible(&mut self, _visible: bool) {}
+
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.0)
+    }
+}
+
+#[test]
+fn elements_only_write_inside_their_area() {
+    let backend = render(
+        8,
+        3,
+        vec![Arc::new(RwLock::new(Overflowing(Rect::new(3, 1, 4, 2))))],
+    );
+    backend.assert_lines(&["  xxxx  ", "  yyyy  ", "        "]);
+}
+
+#[test]
+fn wide_graphemes_cut_at_the_left_edge_are_blanked() {
+    let mut container = Container::new(Rect::new(2, 1, 4, 1));
+    container
+        .elements
+        .push(Arc::new(RwLock::new(text(0, 1, 0, "界ab"))));
+    let backend = render(6, 1, vec![Arc::new(RwLock::new(container))]);
+    backend.assert_lines(&["  ab  "]);
+}
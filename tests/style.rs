@@ -0,0 +1,34 @@
+use charflow::colors::{set_color_depth, ColorDepth, GREY, YELLOW};
+use charflow::style::{Attributes, Style, Underline};
+
+#[test]
+fn attributes_map_to_sgr_codes() {
+    set_color_depth(Some(ColorDepth::TrueColor));
+    assert_eq!(Attributes::default().to_escape(), "");
+    let attributes = Attributes {
+        bold: true,
+        italic: true,
+        underline: Underline::Curly,
+        underline_color: Some(YELLOW),
+        strikethrough: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        attributes.to_escape(),
+        "\x1b[1;3;4:3;9m\x1b[58;2;255;204;0m"
+    );
+}
+
+#[test]
+fn style_resets_before_applying() {
+    set_color_depth(Some(ColorDepth::TrueColor));
+    let style = Style::new(Some(YELLOW), Some(GREY)).with_attributes(Attributes {
+        reverse: true,
+        ..Default::default()
+    });
+    assert_eq!(
+        style.to_escape(),
+        "\x1b[0m\x1b[48;2;53;53;53m\x1b[38;2;255;204;0m\x1b[7m"
+    );
+    assert_eq!(Style::default().to_escape(), "\x1b[0m");
+}
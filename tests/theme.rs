@@ -0,0 +1,140 @@
+use charflow::backend::TestBackend;
+use charflow::colors::{ORANGE, YELLOW};
+use charflow::elements::simple::line_box::LINES_LIGHT;
+use charflow::elements::simple::{Box as LineBox, Button, Text};
+use charflow::input::Action;
+use charflow::style::Attributes;
+use charflow::theme::{set_theme, themed, Role, Theme};
+use charflow::tui::{StaticTUI, TUI};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+
+/// Serializes the tests switching the global theme.
+static THEME_LOCK: Mutex<()> = Mutex::new(());
+
+/// Holds the theme lock and resets the theme when dropped, even if the test panics.
+struct ThemeGuard(#[allow(dead_code)] MutexGuard<'static, ()>);
+
+impl ThemeGuard {
+    fn new() -> ThemeGuard {
+        ThemeGuard(THEME_LOCK.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl Drop for ThemeGuard {
+    fn drop(&mut self) {
+        set_theme(None);
+    }
+}
+
+#[test]
+fn elements_fall_back_to_the_active_theme() {
+    let _guard = ThemeGuard::new();
+    let backend = TestBackend::new(4, 4);
+    let tui = StaticTUI::with_backend(Box::new(backend.clone()));
+    let text = Arc::new(RwLock::new(Text {
+        x: 1,
+        y: 1,
+        z: 0,
+        text_color: Some(YELLOW),
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        text: "hi".to_string(),
+        markup: false,
+    }));
+    let button = Arc::new(RwLock::new(Button {
+        x: 1,
+        y: 2,
+        z: 0,
+        width: 4,
+        height: 1,
+        text_color: None,
+        bg_color: None,
+        selected_text_color: None,
+        selected_bg_color: None,
+        attributes: Attributes::default(),
+        selected_attributes: Attributes::default(),
+        action: Action::default(),
+        selected: false,
+        enabled: true,
+        visible: true,
+        text: "ok".to_string(),
+        markup: false,
+    }));
+    let line_box = Arc::new(RwLock::new(LineBox {
+        x: 1,
+        y: 3,
+        z: 0,
+        width: 4,
+        height: 2,
+        line_color: None,
+        bg_color: None,
+        attributes: Attributes::default(),
+        visible: true,
+        line_set: LINES_LIGHT,
+    }));
+    tui.write().unwrap().elements = vec![text, button.clone(), line_box];
+
+    tui.read().unwrap().update();
+    let screen = backend.get_buffer();
+    assert_eq!(screen.get(1, 1).unwrap().style.bg_color, None);
+    assert_eq!(screen.get(3, 1).unwrap().style.bg_color, None);
+    assert_eq!(screen.get(1, 2).unwrap().style.fg_color, None);
+    assert_eq!(screen.get(1, 3).unwrap().style.bg_color, None);
+
+    let dark = Theme::dark();
+    set_theme(Some(dark));
+    tui.read().unwrap().update();
+    let screen = backend.get_buffer();
+    // own colors win, the rest comes from the theme
+    assert_eq!(screen.get(1, 1).unwrap().style.fg_color, Some(YELLOW));
+    assert_eq!(screen.get(1, 1).unwrap().style.bg_color, Some(dark.surface));
+    assert_eq!(screen.get(3, 1).unwrap().style.bg_color, Some(dark.surface));
+    assert_eq!(screen.get(1, 2).unwrap().style.fg_color, Some(dark.text));
+    assert_eq!(screen.get(1, 3).unwrap().style.fg_color, Some(dark.border));
+    assert_eq!(screen.get(1, 3).unwrap().style.bg_color, Some(dark.surface));
+
+    button.write().unwrap().selected = true;
+    tui.read().unwrap().update();
+    let cell = backend.get_buffer().get(1, 2).unwrap().clone();
+    assert_eq!(cell.style.fg_color, Some(dark.selected_text));
+    assert_eq!(cell.style.bg_color, Some(dark.selected));
+
+    button.write().unwrap().selected = false;
+    button.write().unwrap().enabled = false;
+    set_theme(Some(Theme::light()));
+    tui.read().unwrap().update();
+    let cell = backend.get_buffer().get(1, 2).unwrap().clone();
+    assert_eq!(cell.style.fg_color, Some(Theme::light().disabled));
+
+    // an own color wins over the disabled one of the theme
+    button.write().unwrap().text_color = Some(ORANGE);
+    tui.read().unwrap().update();
+    let cell = backend.get_buffer().get(1, 2).unwrap().clone();
+    assert_eq!(cell.style.fg_color, Some(ORANGE));
+
+    set_theme(None);
+    assert_eq!(themed(Some(ORANGE), Role::Text), Some(ORANGE));
+    assert_eq!(themed(None, Role::Text), None);
+}
+
+#[test]
+fn built_in_themes_are_readable() {
+    for theme in [Theme::dark(), Theme::light(), Theme::high_contrast()] {
+        assert!(theme.text.contrast_ratio(theme.surface) >= 4.5);
+        assert!(theme.selected_text.contrast_ratio(theme.selected) >= 3.0);
+        assert_eq!(theme.get(Role::Error), theme.error);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn themes_load_from_json() {
+    let path = std::env::temp_dir().join(format!("charflow-theme-{}.json", std::process::id()));
+    std::fs::write(&path, r##"{"accent": "#ff0000", "text": "white"}"##).unwrap();
+    let theme = Theme::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(theme.accent, charflow::Color::new(255, 0, 0));
+    assert_eq!(theme.surface, Theme::dark().surface);
+    assert!(Theme::from_file(&path).is_err());
+}
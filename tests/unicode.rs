@@ -0,0 +1,57 @@
+use charflow::buffer::Buffer;
+use charflow::elements::wrap;
+use charflow::style::Style;
+use charflow::unicode::{split_at_width, truncate, width};
+
+#[test]
+fn width_counts_cells() {
+    assert_eq!(width("abc"), 3);
+    assert_eq!(width("日本語"), 6);
+    // e followed by a combining acute accent
+    assert_eq!(width("e\u{301}te\u{301}"), 3);
+}
+
+#[test]
+fn truncation_keeps_graphemes_intact() {
+    assert_eq!(truncate("日本語", 5), "日本");
+    assert_eq!(truncate("e\u{301}e\u{301}", 1), "e\u{301}");
+    assert_eq!(split_at_width("ab日c", 3), ("ab", "日c"));
+}
+
+#[test]
+fn wrap_measures_display_width() {
+    assert_eq!(
+        wrap("日本 語の テキスト", 7),
+        vec!["日本", "語の", "テキス-", "ト"]
+    );
+    assert_eq!(wrap("ünïcödé wörds", 9), vec!["ünïcödé", "wörds"]);
+}
+
+#[test]
+fn wrap_splits_long_words_once() {
+    assert_eq!(wrap("abcdefgh", 4), vec!["abc-", "def-", "gh"]);
+    assert_eq!(wrap("ab", 1), vec!["a", "b"]);
+}
+
+#[test]
+fn wrap_fits_words_exactly_as_wide_as_the_line() {
+    assert_eq!(wrap("hello", 5), vec!["hello"]);
+    assert_eq!(wrap("hello world", 11), vec!["hello world"]);
+    assert_eq!(wrap("hello world", 5), vec!["hello", "world"]);
+}
+
+#[test]
+fn wide_graphemes_cover_two_cells() {
+    let mut buffer = Buffer::new(5, 1);
+    assert_eq!(buffer.set_string(1, 1, "a日b", Style::default()), 4);
+    assert_eq!(buffer.get_lines(), vec!["a日b "]);
+    assert_eq!(buffer.get(3, 1).unwrap().symbol, "");
+
+    // overwriting half of a wide grapheme blanks the other half
+    buffer.set_string(3, 1, "x", Style::default());
+    assert_eq!(buffer.get_lines(), vec!["a xb "]);
+
+    // wide graphemes that don't fit at the edge are replaced
+    buffer.set_string(5, 1, "語", Style::default());
+    assert_eq!(buffer.get_lines(), vec!["a xb "]);
+}